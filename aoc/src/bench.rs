@@ -0,0 +1,67 @@
+use std::fmt::Display;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// How long one stage of a day took over every run.
+pub struct Timings {
+    pub stage: &'static str,
+    samples: Vec<Duration>,
+}
+
+impl Timings {
+    pub fn runs(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples[0]
+    }
+
+    pub fn median(&self) -> Duration {
+        let mid = self.samples.len() / 2;
+        match self.samples.len() % 2 {
+            0 => (self.samples[mid - 1] + self.samples[mid]) / 2,
+            _ => self.samples[mid],
+        }
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples[self.samples.len() - 1]
+    }
+}
+
+/// Times `stage` `runs` times. The result is passed through `black_box` so the
+/// work can't be optimised away.
+pub fn time<T>(stage: &'static str, runs: usize, f: impl Fn() -> T) -> Timings {
+    let mut samples = (0..runs)
+        .map(|_| {
+            let now = Instant::now();
+            black_box(f());
+            now.elapsed()
+        })
+        .collect::<Vec<Duration>>();
+    samples.sort();
+    Timings { stage, samples }
+}
+
+/// A named part to time against an already parsed input.
+pub type Part<'a, P> = (&'static str, &'a dyn Fn(&P) -> Result<String, String>);
+
+/// Times parsing once per run, then each part against a single parse so the
+/// parts don't pay for it again. A part with no answer fails the bench rather
+/// than timing its error.
+pub fn stages<P, E: Display>(
+    runs: usize,
+    input: &str,
+    parse: impl Fn(&str) -> Result<P, E>,
+    parts: &[Part<P>],
+) -> Result<Vec<Timings>, String> {
+    let parsed = parse(input).map_err(|err| err.to_string())?;
+
+    let mut timings = vec![time("parse", runs, || parse(input))];
+    for (stage, part) in parts {
+        part(&parsed)?;
+        timings.push(time(stage, runs, || part(&parsed)));
+    }
+    Ok(timings)
+}
@@ -0,0 +1,102 @@
+use std::fmt::Display;
+
+use num_bigint::BigUint;
+
+use crate::bench::{self, Timings};
+
+// a part either answers or explains why the input couldn't be parsed
+pub type Solver = fn(&str) -> Result<String, String>;
+
+pub type Bench = fn(&str, usize) -> Result<Vec<Timings>, String>;
+
+pub struct Day {
+    pub part_1: Solver,
+    pub part_2: Option<Solver>,
+    pub bench: Bench,
+}
+
+/// What a part returns: an answer, or a `Result` whose error explains why the
+/// input has none.
+pub trait Answer {
+    fn answer(self) -> Result<String, String>;
+}
+
+macro_rules! answer {
+    ($($answer:ty),*) => {
+        $(
+            impl Answer for $answer {
+                fn answer(self) -> Result<String, String> {
+                    Ok(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+answer!(i64, u32, u64, usize, String, BigUint);
+
+impl<T: Display, E: Display> Answer for Result<T, E> {
+    fn answer(self) -> Result<String, String> {
+        self.map(|answer| answer.to_string()).map_err(|err| err.to_string())
+    }
+}
+
+macro_rules! part {
+    ($day:ident, $part:ident) => {
+        |input| match $day::parse(input) {
+            Ok(parsed) => $day::$part(&parsed).answer(),
+            Err(err) => Err(err.to_string()),
+        }
+    };
+}
+
+macro_rules! day {
+    ($day:ident) => {
+        Day {
+            part_1: part!($day, part_1),
+            part_2: Some(part!($day, part_2)),
+            bench: |input, runs| bench::stages(runs, input, $day::parse, &[
+                ("part 1", &|parsed: &$day::Parsed| $day::part_1(parsed).answer()),
+                ("part 2", &|parsed: &$day::Parsed| $day::part_2(parsed).answer()),
+            ]),
+        }
+    };
+    // the last day only has one puzzle
+    ($day:ident, part_1) => {
+        Day {
+            part_1: part!($day, part_1),
+            part_2: None,
+            bench: |input, runs| bench::stages(runs, input, $day::parse, &[
+                ("part 1", &|parsed: &$day::Parsed| $day::part_1(parsed).answer()),
+            ]),
+        }
+    };
+}
+
+pub const DAYS: [Day; 25] = [
+    day!(day_1),
+    day!(day_2),
+    day!(day_3),
+    day!(day_4),
+    day!(day_5),
+    day!(day_6),
+    day!(day_7),
+    day!(day_8),
+    day!(day_9),
+    day!(day_10),
+    day!(day_11),
+    day!(day_12),
+    day!(day_13),
+    day!(day_14),
+    day!(day_15),
+    day!(day_16),
+    day!(day_17),
+    day!(day_18),
+    day!(day_19),
+    day!(day_20),
+    day!(day_21),
+    day!(day_22),
+    day!(day_23),
+    day!(day_24),
+    day!(day_25, part_1),
+];
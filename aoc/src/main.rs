@@ -0,0 +1,483 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
+
+use client::Verdict;
+use common::Input;
+
+mod bench;
+mod days;
+mod site;
+mod verify;
+
+use days::{Day, Solver, DAYS};
+
+const USAGE: &str = "usage: aoc run <day|all> [--part <1|2>] [--input <path|-> | --text <input>] [--format <text|json>]
+       aoc verify <answers> [--inputs <dir>]
+       aoc bench <day|all> [--runs <n>] [--input <path|-> | --text <input>] [--format <text|csv|json>]
+       aoc fetch <day|all>
+       aoc submit <day> <part> [--answer <value>] [--input <path|-> | --text <input>]";
+
+enum Selection {
+    All,
+    Day(usize),
+}
+
+#[derive(PartialEq)]
+enum Format {
+    Text,
+    Csv,
+    Json,
+}
+
+enum Command {
+    Run {
+        days: Selection,
+        part: Option<u32>,
+        input: Option<Input>,
+        format: Format,
+    },
+    Verify {
+        answers: PathBuf,
+        inputs: PathBuf,
+    },
+    Bench {
+        days: Selection,
+        runs: usize,
+        input: Option<Input>,
+        format: Format,
+    },
+    Fetch {
+        days: Selection,
+    },
+    Submit {
+        day: usize,
+        part: u32,
+        answer: Option<String>,
+        input: Option<Input>,
+    },
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    match command {
+        Command::Run { days: Selection::All, part, format, .. } => {
+            for day in 1..=DAYS.len() {
+                let input = Input::File(input_path(&default_inputs(), day));
+                run_day(day, part, &input, &format);
+            }
+        },
+        Command::Run { days: Selection::Day(day), part, input, format } => {
+            let input = input.unwrap_or_else(|| Input::File(input_path(&default_inputs(), day)));
+            run_day(day, part, &input, &format);
+        },
+        Command::Verify { answers, inputs } => {
+            let expected = match verify::load_answers(&answers) {
+                Ok(expected) => expected,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    process::exit(1);
+                }
+            };
+            if !verify::verify(&expected, &inputs) {
+                process::exit(1);
+            }
+        },
+        Command::Bench { days, runs, input, format } => {
+            if let Format::Csv = format {
+                println!("day,stage,runs,min_ms,median_ms,max_ms");
+            }
+            match days {
+                Selection::All => {
+                    for day in 1..=DAYS.len() {
+                        let input = Input::File(input_path(&default_inputs(), day));
+                        bench_day(day, runs, &input, &format);
+                    }
+                },
+                Selection::Day(day) => {
+                    let input = input.unwrap_or_else(|| Input::File(input_path(&default_inputs(), day)));
+                    bench_day(day, runs, &input, &format);
+                },
+            }
+        },
+        Command::Fetch { days } => {
+            let days = match days {
+                Selection::All => (1..=DAYS.len()).collect(),
+                Selection::Day(day) => vec![day],
+            };
+            if let Err(err) = site::fetch(&days, &default_inputs()) {
+                eprintln!("{}", err);
+                process::exit(1);
+            }
+        },
+        Command::Submit { day, part, answer, input } => {
+            let answer = answer.unwrap_or_else(|| {
+                let input = input.unwrap_or_else(|| Input::File(input_path(&default_inputs(), day)));
+                solve_part(day, part, &input)
+            });
+            match site::submit(day, part, &answer, &submissions_path()) {
+                Ok((verdict, remembered)) => {
+                    let seen = if remembered { " (submitted before)" } else { "" };
+                    println!("day {} part {}: {} is {}{}", day, part, answer, verdict, seen);
+                    if verdict != Verdict::Right {
+                        process::exit(1);
+                    }
+                },
+                Err(err) => {
+                    eprintln!("day {}: {}", day, err);
+                    process::exit(1);
+                }
+            }
+        },
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.first().map(|arg| arg.as_str()) {
+        Some("run") => parse_run(args),
+        Some("verify") => parse_verify(args),
+        Some("bench") => parse_bench(args),
+        Some("fetch") => parse_fetch(args),
+        Some("submit") => parse_submit(args),
+        Some(other) => Err(format!("unknown command '{}'", other)),
+        None => Err("missing command".to_owned()),
+    }
+}
+
+fn parse_run(args: &[String]) -> Result<Command, String> {
+    let days = match args.get(1).map(|arg| arg.as_str()) {
+        Some("all") => Selection::All,
+        Some(day) => Selection::Day(parse_day(day)?),
+        None => return Err("missing day".to_owned()),
+    };
+
+    let mut part = None;
+    let mut input = None;
+    let mut format = Format::Text;
+    let mut rest = args[2..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--part" => {
+                part = match rest.next().map(|p| p.as_str()) {
+                    Some("1") => Some(1),
+                    Some("2") => Some(2),
+                    Some(other) => return Err(format!("invalid part '{}'", other)),
+                    None => return Err("--part needs a value".to_owned()),
+                }
+            },
+            "--input" => {
+                input = match rest.next() {
+                    Some(arg) => Some(Input::from_arg(arg)),
+                    None => return Err("--input needs a value".to_owned()),
+                }
+            },
+            "--text" => {
+                input = match rest.next() {
+                    Some(text) => Some(Input::Inline(text.to_owned())),
+                    None => return Err("--text needs a value".to_owned()),
+                }
+            },
+            "--format" => format = parse_format(rest.next(), &[Format::Text, Format::Json])?,
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    if input.is_some() {
+        if let Selection::All = days {
+            return Err("--input and --text need a single day".to_owned());
+        }
+    }
+
+    Ok(Command::Run { days, part, input, format })
+}
+
+fn parse_verify(args: &[String]) -> Result<Command, String> {
+    let answers = match args.get(1) {
+        Some(path) => PathBuf::from(path),
+        None => return Err("missing answers file".to_owned()),
+    };
+
+    let mut inputs = default_inputs();
+    let mut rest = args[2..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--inputs" => {
+                inputs = match rest.next() {
+                    Some(dir) => PathBuf::from(dir),
+                    None => return Err("--inputs needs a directory".to_owned()),
+                }
+            },
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    Ok(Command::Verify { answers, inputs })
+}
+
+fn parse_bench(args: &[String]) -> Result<Command, String> {
+    let days = match args.get(1).map(|arg| arg.as_str()) {
+        Some("all") => Selection::All,
+        Some(day) => Selection::Day(parse_day(day)?),
+        None => return Err("missing day".to_owned()),
+    };
+
+    let mut runs = 10;
+    let mut input = None;
+    let mut format = Format::Text;
+    let mut rest = args[2..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--runs" => {
+                runs = match rest.next().map(|n| n.parse::<usize>()) {
+                    Some(Ok(n)) if n > 0 => n,
+                    Some(_) => return Err("--runs needs a positive number".to_owned()),
+                    None => return Err("--runs needs a value".to_owned()),
+                }
+            },
+            "--input" => {
+                input = match rest.next() {
+                    Some(arg) => Some(Input::from_arg(arg)),
+                    None => return Err("--input needs a value".to_owned()),
+                }
+            },
+            "--text" => {
+                input = match rest.next() {
+                    Some(text) => Some(Input::Inline(text.to_owned())),
+                    None => return Err("--text needs a value".to_owned()),
+                }
+            },
+            "--format" => format = parse_format(rest.next(), &[Format::Text, Format::Csv, Format::Json])?,
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    if input.is_some() {
+        if let Selection::All = days {
+            return Err("--input and --text need a single day".to_owned());
+        }
+    }
+
+    Ok(Command::Bench { days, runs, input, format })
+}
+
+fn parse_fetch(args: &[String]) -> Result<Command, String> {
+    let days = match args.get(1).map(|arg| arg.as_str()) {
+        Some("all") => Selection::All,
+        Some(day) => Selection::Day(parse_day(day)?),
+        None => return Err("missing day".to_owned()),
+    };
+    if let Some(other) = args.get(2) {
+        return Err(format!("unknown argument '{}'", other));
+    }
+
+    Ok(Command::Fetch { days })
+}
+
+fn parse_submit(args: &[String]) -> Result<Command, String> {
+    let day = match args.get(1) {
+        Some(day) => parse_day(day)?,
+        None => return Err("missing day".to_owned()),
+    };
+    let part = match args.get(2).map(|p| p.as_str()) {
+        Some("1") => 1,
+        Some("2") if DAYS[day - 1].part_2.is_some() => 2,
+        Some(other) => return Err(format!("invalid part '{}'", other)),
+        None => return Err("missing part".to_owned()),
+    };
+
+    let mut answer = None;
+    let mut input = None;
+    let mut rest = args[3..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--answer" => {
+                answer = match rest.next() {
+                    Some(value) => Some(value.to_owned()),
+                    None => return Err("--answer needs a value".to_owned()),
+                }
+            },
+            "--input" => {
+                input = match rest.next() {
+                    Some(arg) => Some(Input::from_arg(arg)),
+                    None => return Err("--input needs a value".to_owned()),
+                }
+            },
+            "--text" => {
+                input = match rest.next() {
+                    Some(text) => Some(Input::Inline(text.to_owned())),
+                    None => return Err("--text needs a value".to_owned()),
+                }
+            },
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    if answer.is_some() && input.is_some() {
+        return Err("--answer and --input can't be used together".to_owned());
+    }
+
+    Ok(Command::Submit { day, part, answer, input })
+}
+
+// each command only supports some of the formats
+fn parse_format(arg: Option<&String>, supported: &[Format]) -> Result<Format, String> {
+    let format = match arg.map(|f| f.as_str()) {
+        Some("text") => Format::Text,
+        Some("csv") => Format::Csv,
+        Some("json") => Format::Json,
+        Some(other) => return Err(format!("invalid format '{}'", other)),
+        None => return Err("--format needs a value".to_owned()),
+    };
+    match supported.contains(&format) {
+        true => Ok(format),
+        false => Err(format!("format '{}' isn't supported here", arg.unwrap())),
+    }
+}
+
+fn parse_day(day: &str) -> Result<usize, String> {
+    match day.parse::<usize>() {
+        Ok(val) if val >= 1 && val <= DAYS.len() => Ok(val),
+        _ => Err(format!("invalid day '{}'", day)),
+    }
+}
+
+fn run_day(day: usize, part: Option<u32>, input: &Input, format: &Format) {
+    let solver: &Day = &DAYS[day - 1];
+    let input = match input.read() {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("day {}: failed to read {}: {}", day, input, err);
+            process::exit(1);
+        }
+    };
+
+    if part != Some(2) {
+        run_part(day, 1, solver.part_1, &input, format);
+    }
+    if part != Some(1) {
+        if let Some(part_2) = solver.part_2 {
+            run_part(day, 2, part_2, &input, format);
+        }
+    }
+}
+
+fn run_part(day: usize, part: u32, solve: Solver, input: &str, format: &Format) {
+    let now = Instant::now();
+    match solve(input) {
+        Ok(answer) => match format {
+            Format::Json => println!(
+                "{{\"day\":{},\"part\":{},\"answer\":{},\"duration_ms\":{:.3}}}",
+                day, part, json_string(&answer), millis(now.elapsed())
+            ),
+            _ => println!("day {} part {}: {} ({:?})", day, part, answer, now.elapsed()),
+        },
+        Err(err) => {
+            eprintln!("day {}: {}", day, err);
+            process::exit(1);
+        }
+    }
+}
+
+// the answer to submit when none is given, exits like run on failure
+fn solve_part(day: usize, part: u32, input: &Input) -> String {
+    let contents = match input.read() {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("day {}: failed to read {}: {}", day, input, err);
+            process::exit(1);
+        }
+    };
+    let solve = match part {
+        1 => DAYS[day - 1].part_1,
+        _ => DAYS[day - 1].part_2.expect("parse_submit only allows parts that exist"),
+    };
+    match solve(&contents) {
+        Ok(answer) => answer,
+        Err(err) => {
+            eprintln!("day {}: {}", day, err);
+            process::exit(1);
+        }
+    }
+}
+
+fn bench_day(day: usize, runs: usize, input: &Input, format: &Format) {
+    let input = match input.read() {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("day {}: failed to read {}: {}", day, input, err);
+            process::exit(1);
+        }
+    };
+
+    let timings = match (DAYS[day - 1].bench)(&input, runs) {
+        Ok(timings) => timings,
+        Err(err) => {
+            eprintln!("day {}: {}", day, err);
+            process::exit(1);
+        }
+    };
+
+    for timing in timings {
+        match format {
+            Format::Text => println!(
+                "day {} {}: min {:?}, median {:?}, max {:?} ({} runs)",
+                day, timing.stage, timing.min(), timing.median(), timing.max(), timing.runs()
+            ),
+            Format::Csv => println!(
+                "{},{},{},{:.3},{:.3},{:.3}",
+                day, timing.stage, timing.runs(),
+                millis(timing.min()), millis(timing.median()), millis(timing.max())
+            ),
+            Format::Json => println!(
+                "{{\"day\":{},\"stage\":{},\"runs\":{},\"min_ms\":{:.3},\"median_ms\":{:.3},\"max_ms\":{:.3}}}",
+                day, json_string(timing.stage), timing.runs(),
+                millis(timing.min()), millis(timing.median()), millis(timing.max())
+            ),
+        }
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+// answers are mostly numbers, but some days answer with a list or a code
+fn json_string(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// default inputs live next to each day's crate, so the runner works from any directory
+fn default_inputs() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+// answers sent so far, so the same one is never sent twice
+fn submissions_path() -> PathBuf {
+    default_inputs().join("submissions.txt")
+}
+
+// other accounts' inputs are laid out like the repo: <dir>/day_<n>/input.txt
+fn input_path(inputs: &Path, day: usize) -> PathBuf {
+    inputs.join(format!("day_{}", day)).join("input.txt")
+}
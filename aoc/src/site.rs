@@ -0,0 +1,42 @@
+use std::env;
+use std::path::Path;
+
+use client::{Client, Submissions, Verdict};
+
+use crate::input_path;
+
+const SESSION: &str = "AOC_SESSION";
+
+fn client() -> Result<Client, String> {
+    match env::var(SESSION) {
+        Ok(session) if !session.trim().is_empty() => Ok(Client::new(session.trim())),
+        _ => Err(format!("{} needs to hold your adventofcode.com session cookie", SESSION)),
+    }
+}
+
+/// Downloads the input for each of `days` that isn't in `inputs` yet.
+pub fn fetch(days: &[usize], inputs: &Path) -> Result<(), String> {
+    let client = client()?;
+    for &day in days {
+        let path = input_path(inputs, day);
+        match client.fetch_input(day, &path) {
+            Ok(true) => println!("day {}: saved to {}", day, path.display()),
+            Ok(false) => println!("day {}: already at {}", day, path.display()),
+            Err(err) => return Err(format!("day {}: {}", day, err)),
+        }
+    }
+    Ok(())
+}
+
+/// Submits `answer` unless an earlier submission in `submissions` already
+/// says how it'd go. Returns the verdict and whether it was remembered.
+pub fn submit(day: usize, part: u32, answer: &str, submissions: &Path) -> Result<(Verdict, bool), String> {
+    let mut submissions = Submissions::load(submissions).map_err(|err| err.to_string())?;
+    if let Some(verdict) = submissions.check(day, part, answer) {
+        return Ok((verdict, true));
+    }
+
+    let verdict = client()?.submit(day, part, answer).map_err(|err| err.to_string())?;
+    submissions.record(day, part, answer, &verdict).map_err(|err| err.to_string())?;
+    Ok((verdict, false))
+}
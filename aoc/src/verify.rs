@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::days::DAYS;
+use crate::input_path;
+
+/// Known answers keyed by (day, part).
+pub type Expected = HashMap<(usize, u32), String>;
+
+/// Reads a table of `<day> <part> <answer>` lines. Blank lines and lines
+/// starting with `#` are skipped.
+pub fn load_answers(path: &Path) -> Result<Expected, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+
+    let mut expected = Expected::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+        let entry = match fields[..] {
+            [day, part, answer] => match (day.parse::<usize>(), part.parse::<u32>()) {
+                (Ok(day), Ok(part)) if day >= 1 && day <= DAYS.len() && (part == 1 || part == 2) => Some((day, part, answer)),
+                _ => None,
+            },
+            _ => None,
+        };
+        match entry {
+            Some((day, part, answer)) => expected.insert((day, part), answer.to_owned()),
+            None => return Err(format!("{} line {}: expected '<day> <part> <answer>', found '{}'", path.display(), i + 1, line)),
+        };
+    }
+    Ok(expected)
+}
+
+/// Runs every day against `inputs` and checks the answers. Returns whether
+/// nothing failed; parts without an input or an expected answer are skipped.
+pub fn verify(expected: &Expected, inputs: &Path) -> bool {
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut skipped = 0;
+
+    for (i, day) in DAYS.iter().enumerate() {
+        let number = i + 1;
+        let path = input_path(inputs, number);
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(_) => {
+                println!("day {}: skip (no input at {})", number, path.display());
+                skipped += 1 + day.part_2.is_some() as usize;
+                continue;
+            }
+        };
+
+        let parts = [Some(day.part_1), day.part_2];
+        for (part, solve) in (1..).zip(parts.iter()) {
+            let solve = match solve {
+                Some(solve) => solve,
+                None => continue,
+            };
+            let answer = match expected.get(&(number, part)) {
+                Some(answer) => answer,
+                None => {
+                    println!("day {} part {}: skip (no expected answer)", number, part);
+                    skipped += 1;
+                    continue;
+                }
+            };
+
+            let now = Instant::now();
+            let result = solve(&input);
+            let elapsed = now.elapsed();
+            match result {
+                Ok(actual) if &actual == answer => {
+                    println!("day {} part {}: pass ({:?})", number, part, elapsed);
+                    passed += 1;
+                },
+                Ok(actual) => {
+                    println!("day {} part {}: FAIL expected {}, got {} ({:?})", number, part, answer, actual, elapsed);
+                    failed += 1;
+                },
+                Err(err) => {
+                    println!("day {} part {}: FAIL {}", number, part, err);
+                    failed += 1;
+                },
+            }
+        }
+    }
+
+    println!("{} passed, {} failed, {} skipped", passed, failed, skipped);
+    failed == 0
+}
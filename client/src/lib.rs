@@ -0,0 +1,117 @@
+//! Fetches puzzle inputs from adventofcode.com and submits answers to it.
+
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+mod submissions;
+mod verdict;
+
+pub use submissions::Submissions;
+pub use verdict::Verdict;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u32 = 2020;
+
+// the site asks automated tools to say who they are
+const USER_AGENT: &str = "aoc-2020 runner by kevindhanna@live.com";
+
+#[derive(Debug)]
+pub enum Error {
+    /// The server couldn't be reached.
+    Transport(String),
+    /// The server answered with something other than success, usually a
+    /// missing or expired session or a puzzle that isn't unlocked yet.
+    Status(u16, String),
+    Io(io::Error),
+    /// A page that doesn't say what happened to a submission.
+    UnexpectedResponse(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Transport(err) => write!(f, "couldn't reach the server: {}", err),
+            Error::Status(status, body) => write!(f, "server answered {}: {}", status, body.trim()),
+            Error::Io(err) => write!(f, "{}", err),
+            Error::UnexpectedResponse(page) => write!(f, "unexpected response: {}", page),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+impl From<ureq::Error> for Error {
+    fn from(err: ureq::Error) -> Error {
+        match err {
+            ureq::Error::Status(status, response) => Error::Status(status, response.into_string().unwrap_or_default()),
+            ureq::Error::Transport(transport) => Error::Transport(transport.to_string()),
+        }
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// `session` is the value of the site's `session` cookie.
+    pub fn new(session: &str) -> Client {
+        Client::with_base_url(BASE_URL, session)
+    }
+
+    /// Talks to a server other than adventofcode.com, like a local stand-in.
+    pub fn with_base_url(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.to_owned(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    pub fn input(&self, day: usize) -> Result<String, Error> {
+        let response = self.agent.get(&self.url(day, "input"))
+                                 .set("Cookie", &self.cookie())
+                                 .call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Downloads the input for `day` to `path` unless it's already there.
+    /// Returns whether it was downloaded.
+    pub fn fetch_input(&self, day: usize, path: &Path) -> Result<bool, Error> {
+        if path.exists() {
+            return Ok(false);
+        }
+        let input = self.input(day)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, input)?;
+        Ok(true)
+    }
+
+    pub fn submit(&self, day: usize, part: u32, answer: &str) -> Result<Verdict, Error> {
+        let response = self.agent.post(&self.url(day, "answer"))
+                                 .set("Cookie", &self.cookie())
+                                 .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+        Verdict::parse(&response.into_string()?)
+    }
+
+    fn url(&self, day: usize, page: &str) -> String {
+        format!("{}/{}/day/{}/{}", self.base_url, YEAR, day, page)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
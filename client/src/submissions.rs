@@ -0,0 +1,118 @@
+use std::fs;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::{Error, Verdict};
+
+struct Submission {
+    day: usize,
+    part: u32,
+    answer: String,
+    verdict: Verdict,
+}
+
+/// Every answer submitted so far and what the site said about it, kept in a
+/// file of `<day> <part> <verdict> <answer>` lines.
+pub struct Submissions {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl Submissions {
+    /// A missing file means nothing has been submitted yet.
+    pub fn load(path: &Path) -> Result<Submissions, Error> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        let submissions = contents.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                read_submission(line).ok_or_else(|| {
+                    let message = format!("{}: expected '<day> <part> <verdict> <answer>', found '{}'", path.display(), line);
+                    Error::Io(io::Error::new(io::ErrorKind::InvalidData, message))
+                })
+            })
+            .collect::<Result<Vec<Submission>, Error>>()?;
+
+        Ok(Submissions { path: path.to_owned(), submissions })
+    }
+
+    /// What the site would say about `answer`, if earlier submissions already
+    /// settle it: the same answer again, a part that's already solved, or a
+    /// number past one that was too high or too low.
+    pub fn check(&self, day: usize, part: u32, answer: &str) -> Option<Verdict> {
+        let earlier = self.submissions.iter().filter(|s| s.day == day && s.part == part);
+        let mut verdict = None;
+        for submission in earlier {
+            if submission.answer == answer {
+                return Some(submission.verdict.clone());
+            }
+            let beyond = match (answer.parse::<i128>(), submission.answer.parse::<i128>()) {
+                (Ok(answer), Ok(earlier)) => match submission.verdict {
+                    Verdict::TooHigh => answer >= earlier,
+                    Verdict::TooLow => answer <= earlier,
+                    _ => false,
+                },
+                _ => false,
+            };
+            if beyond {
+                verdict = Some(submission.verdict.clone());
+            }
+            if submission.verdict == Verdict::Right {
+                verdict = Some(Verdict::Wrong);
+            }
+        }
+        verdict
+    }
+
+    /// Remembers a verdict. Rate limits and wrong levels say nothing about the
+    /// answer, so they aren't kept.
+    pub fn record(&mut self, day: usize, part: u32, answer: &str, verdict: &Verdict) -> Result<(), Error> {
+        let token = match to_token(verdict) {
+            Some(token) => token,
+            None => return Ok(()),
+        };
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{} {} {} {}", day, part, token, answer)?;
+        self.submissions.push(Submission { day, part, answer: answer.to_owned(), verdict: verdict.clone() });
+        Ok(())
+    }
+}
+
+fn read_submission(line: &str) -> Option<Submission> {
+    let fields = line.splitn(4, ' ').collect::<Vec<&str>>();
+    match fields[..] {
+        [day, part, verdict, answer] => Some(Submission {
+            day: day.parse().ok()?,
+            part: part.parse().ok()?,
+            answer: answer.to_owned(),
+            verdict: from_token(verdict)?,
+        }),
+        _ => None,
+    }
+}
+
+fn to_token(verdict: &Verdict) -> Option<&'static str> {
+    match verdict {
+        Verdict::Right => Some("right"),
+        Verdict::Wrong => Some("wrong"),
+        Verdict::TooHigh => Some("too-high"),
+        Verdict::TooLow => Some("too-low"),
+        Verdict::RateLimited(_) | Verdict::WrongLevel => None,
+    }
+}
+
+fn from_token(token: &str) -> Option<Verdict> {
+    match token {
+        "right" => Some(Verdict::Right),
+        "wrong" => Some(Verdict::Wrong),
+        "too-high" => Some(Verdict::TooHigh),
+        "too-low" => Some(Verdict::TooLow),
+        _ => None,
+    }
+}
@@ -0,0 +1,90 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::Error;
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Answered too soon after the last wrong answer, with how long is left
+    /// to wait when the page says.
+    RateLimited(Option<Duration>),
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page returned for a submission.
+    pub fn parse(page: &str) -> Result<Verdict, Error> {
+        if page.contains("That's the right answer") {
+            return Ok(Verdict::Right);
+        }
+        if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                return Ok(Verdict::TooHigh);
+            }
+            if page.contains("your answer is too low") {
+                return Ok(Verdict::TooLow);
+            }
+            return Ok(Verdict::Wrong);
+        }
+        if page.contains("You gave an answer too recently") {
+            return Ok(Verdict::RateLimited(wait(page)));
+        }
+        if page.contains("You don't seem to be solving the right level") {
+            return Ok(Verdict::WrongLevel);
+        }
+        Err(Error::UnexpectedResponse(summary(page)))
+    }
+}
+
+// "You have 1m 5s left to wait."
+fn wait(page: &str) -> Option<Duration> {
+    let (_, rest) = page.split_once("You have ")?;
+    let (left, _) = rest.split_once(" left to wait")?;
+    left.split_whitespace()
+        .map(|amount| match (amount.strip_suffix('s'), amount.strip_suffix('m')) {
+            (Some(seconds), _) => seconds.parse::<u64>().ok(),
+            (_, Some(minutes)) => minutes.parse::<u64>().ok().map(|n| n * 60),
+            _ => None,
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+// the text of the page without its markup, short enough for an error message
+fn summary(page: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in page.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {},
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+    match text.char_indices().nth(200) {
+        Some((i, _)) => format!("{}...", &text[..i]),
+        None => text,
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Right => write!(f, "right"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::RateLimited(Some(wait)) => write!(f, "rate limited, wait {}s", wait.as_secs()),
+            Verdict::RateLimited(None) => write!(f, "rate limited"),
+            Verdict::WrongLevel => write!(f, "already solved or not unlocked yet"),
+        }
+    }
+}
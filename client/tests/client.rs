@@ -0,0 +1,179 @@
+use std::env;
+use std::fs;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+use client::{Client, Error, Submissions, Verdict};
+
+mod server;
+
+use server::StandIn;
+
+const SESSION: &str = "53616c7465645f5f";
+
+fn page(message: &str) -> String {
+    format!("<!DOCTYPE html>\n<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>", message)
+}
+
+fn answering(message: &'static str) -> StandIn {
+    StandIn::start(move |_| (200, page(message)))
+}
+
+fn scratch(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-client-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn downloads_input_with_the_session() {
+    let server = StandIn::start(|_| (200, "1721\n979\n366\n".to_owned()));
+    let client = Client::with_base_url(server.url(), SESSION);
+
+    assert_eq!(client.input(1).unwrap(), "1721\n979\n366\n");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/2020/day/1/input");
+    assert_eq!(requests[0].header("cookie"), Some("session=53616c7465645f5f"));
+    assert!(requests[0].header("user-agent").unwrap().contains("kevindhanna@live.com"));
+}
+
+#[test]
+fn caches_fetched_input() {
+    let server = StandIn::start(|_| (200, "..#\n#..\n".to_owned()));
+    let client = Client::with_base_url(server.url(), SESSION);
+    let path = scratch("cache").join("day_3").join("input.txt");
+
+    assert!(client.fetch_input(3, &path).unwrap());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "..#\n#..\n");
+
+    assert!(!client.fetch_input(3, &path).unwrap());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn reports_a_bad_session() {
+    let server = StandIn::start(|_| (400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".to_owned()));
+    let client = Client::with_base_url(server.url(), "expired");
+    let path = scratch("bad-session").join("input.txt");
+
+    match client.fetch_input(1, &path) {
+        Err(Error::Status(400, body)) => assert!(body.contains("Please log in")),
+        other => panic!("expected a 400, got {:?}", other),
+    }
+    assert!(!path.exists());
+}
+
+#[test]
+fn reports_an_unreachable_server() {
+    let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let client = Client::with_base_url(&format!("http://127.0.0.1:{}", port), SESSION);
+
+    match client.input(1) {
+        Err(Error::Transport(_)) => {},
+        other => panic!("expected a transport error, got {:?}", other),
+    }
+}
+
+#[test]
+fn submits_the_answer_as_a_form() {
+    let server = answering("That's the right answer!  You are one gold star closer to saving your vacation.");
+    let client = Client::with_base_url(server.url(), SESSION);
+
+    assert_eq!(client.submit(8, 2, "1121").unwrap(), Verdict::Right);
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2020/day/8/answer");
+    assert_eq!(requests[0].header("content-type"), Some("application/x-www-form-urlencoded"));
+    assert_eq!(requests[0].body, "level=2&answer=1121");
+}
+
+#[test]
+fn reads_wrong_answers() {
+    let too_high = answering("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again.");
+    let too_low = answering("That's not the right answer; your answer is too low.  Please wait one minute before trying again.");
+    let wrong = answering("That's not the right answer.  If you're stuck, make sure you're using the full input data.");
+
+    assert_eq!(Client::with_base_url(too_high.url(), SESSION).submit(1, 1, "9000").unwrap(), Verdict::TooHigh);
+    assert_eq!(Client::with_base_url(too_low.url(), SESSION).submit(1, 1, "9").unwrap(), Verdict::TooLow);
+    assert_eq!(Client::with_base_url(wrong.url(), SESSION).submit(21, 2, "abc,def").unwrap(), Verdict::Wrong);
+}
+
+#[test]
+fn reads_rate_limits() {
+    let server = answering("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.");
+    let client = Client::with_base_url(server.url(), SESSION);
+
+    assert_eq!(client.submit(1, 1, "12").unwrap(), Verdict::RateLimited(Some(Duration::from_secs(65))));
+
+    let server = answering("You gave an answer too recently.");
+    let client = Client::with_base_url(server.url(), SESSION);
+    assert_eq!(client.submit(1, 1, "12").unwrap(), Verdict::RateLimited(None));
+}
+
+#[test]
+fn reads_wrong_levels() {
+    let server = answering("You don't seem to be solving the right level.  Did you already complete it?");
+    let client = Client::with_base_url(server.url(), SESSION);
+
+    assert_eq!(client.submit(1, 1, "12").unwrap(), Verdict::WrongLevel);
+}
+
+#[test]
+fn rejects_unknown_pages() {
+    let server = answering("Something <em>else</em> entirely.");
+    let client = Client::with_base_url(server.url(), SESSION);
+
+    match client.submit(1, 1, "12") {
+        Err(Error::UnexpectedResponse(text)) => assert_eq!(text, "Something else entirely."),
+        other => panic!("expected an unexpected response, got {:?}", other),
+    }
+}
+
+#[test]
+fn remembers_submissions() {
+    let path = scratch("remember").join("submissions.txt");
+    let mut submissions = Submissions::load(&path).unwrap();
+    assert_eq!(submissions.check(1, 1, "100"), None);
+
+    submissions.record(1, 1, "100", &Verdict::TooHigh).unwrap();
+    submissions.record(1, 1, "10", &Verdict::TooLow).unwrap();
+    submissions.record(1, 1, "50", &Verdict::Wrong).unwrap();
+    submissions.record(1, 1, "12", &Verdict::RateLimited(None)).unwrap();
+
+    let submissions = Submissions::load(&path).unwrap();
+    assert_eq!(submissions.check(1, 1, "50"), Some(Verdict::Wrong));
+    assert_eq!(submissions.check(1, 1, "100"), Some(Verdict::TooHigh));
+    assert_eq!(submissions.check(1, 1, "250"), Some(Verdict::TooHigh));
+    assert_eq!(submissions.check(1, 1, "3"), Some(Verdict::TooLow));
+    assert_eq!(submissions.check(1, 1, "12"), None);
+    assert_eq!(submissions.check(1, 2, "50"), None);
+    assert_eq!(submissions.check(2, 1, "50"), None);
+}
+
+#[test]
+fn knows_solved_parts() {
+    let path = scratch("solved").join("submissions.txt");
+    let mut submissions = Submissions::load(&path).unwrap();
+    submissions.record(21, 2, "abc,def", &Verdict::Right).unwrap();
+
+    assert_eq!(submissions.check(21, 2, "abc,def"), Some(Verdict::Right));
+    assert_eq!(submissions.check(21, 2, "abc,xyz"), Some(Verdict::Wrong));
+}
+
+#[test]
+fn rejects_a_corrupt_submissions_file() {
+    let path = scratch("corrupt").join("submissions.txt");
+    fs::write(&path, "1 1 right 100\nnonsense\n").unwrap();
+
+    match Submissions::load(&path) {
+        Err(Error::Io(err)) => assert!(err.to_string().contains("nonsense")),
+        other => panic!("expected an error, got {:?}", other.map(|_| ())),
+    }
+}
@@ -0,0 +1,414 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// Where cells are and which cells neighbour them.
+pub trait Topology {
+    type Cell: Copy + Eq + Hash + Ord;
+
+    /// Calls `visit` with each neighbour of `cell`.
+    fn neighbours(&self, cell: &Self::Cell, visit: impl FnMut(Self::Cell));
+
+    /// Every cell, for topologies that have a finite number of them. Only
+    /// needed by rules that bring cells to life with no live neighbours.
+    fn cells(&self) -> Option<Vec<Self::Cell>> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Neighbourhood {
+    /// Every cell touching this one, diagonals included.
+    Moore,
+    /// Only the cells one step along a single axis.
+    VonNeumann,
+}
+
+/// An unbounded square grid in `N` dimensions.
+pub struct Lattice<const N: usize> {
+    offsets: Vec<[i32; N]>,
+}
+
+impl<const N: usize> Lattice<N> {
+    pub fn new(neighbourhood: Neighbourhood) -> Lattice<N> {
+        let mut offsets = vec![[0; N]];
+        for axis in 0..N {
+            offsets = offsets.iter()
+                .flat_map(|offset| (-1..=1).map(move |d| {
+                    let mut offset = *offset;
+                    offset[axis] = d;
+                    offset
+                }))
+                .collect();
+        }
+        offsets.retain(|offset| {
+            let moved = offset.iter().filter(|d| **d != 0).count();
+            match neighbourhood {
+                Neighbourhood::Moore => moved > 0,
+                Neighbourhood::VonNeumann => moved == 1,
+            }
+        });
+        Lattice { offsets }
+    }
+}
+
+impl<const N: usize> Topology for Lattice<N> {
+    type Cell = [i32; N];
+
+    fn neighbours(&self, cell: &[i32; N], mut visit: impl FnMut([i32; N])) {
+        for offset in &self.offsets {
+            let mut neighbour = *cell;
+            for (axis, d) in offset.iter().enumerate() {
+                neighbour[axis] += d;
+            }
+            visit(neighbour);
+        }
+    }
+}
+
+/// Cube coordinates of an unbounded hex grid, `x + y + z` is always 0.
+pub const HEX_DIRECTIONS: [(i32, i32, i32); 6] = [
+    (1, -1, 0),
+    (1, 0, -1),
+    (0, 1, -1),
+    (-1, 1, 0),
+    (-1, 0, 1),
+    (0, -1, 1),
+];
+
+pub struct Hex;
+
+impl Topology for Hex {
+    type Cell = (i32, i32, i32);
+
+    fn neighbours(&self, &(x, y, z): &(i32, i32, i32), mut visit: impl FnMut((i32, i32, i32))) {
+        for (dx, dy, dz) in HEX_DIRECTIONS.iter() {
+            visit((x + dx, y + dy, z + dz));
+        }
+    }
+}
+
+/// A finite set of cells numbered from 0, each with its own list of neighbours.
+pub struct Graph {
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    pub fn new(neighbours: Vec<Vec<usize>>) -> Graph {
+        Graph { neighbours }
+    }
+}
+
+impl Topology for Graph {
+    type Cell = usize;
+
+    fn neighbours(&self, cell: &usize, visit: impl FnMut(usize)) {
+        self.neighbours[*cell].iter().copied().for_each(visit);
+    }
+
+    fn cells(&self) -> Option<Vec<usize>> {
+        Some((0..self.neighbours.len()).collect())
+    }
+}
+
+/// A dead cell comes to life with a `birth` number of live neighbours, a live
+/// cell stays alive with a `survival` number of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+
+impl Rule {
+    pub fn new(birth: &[usize], survival: &[usize]) -> Rule {
+        Rule { birth: birth.to_vec(), survival: survival.to_vec() }
+    }
+
+    fn lives(&self, alive: bool, neighbours: usize) -> bool {
+        match alive {
+            true => self.survival.contains(&neighbours),
+            false => self.birth.contains(&neighbours),
+        }
+    }
+}
+
+/// The usual `B3/S23` notation, one digit per neighbour count. Bigger
+/// neighbourhoods need counts past 9, so a list with a comma in it is whole
+/// numbers instead, `B3,12/S2,3`, and a single one ends with a comma, `B12,/S2`.
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(rule: &str) -> Result<Rule, String> {
+        let invalid = || format!("invalid rule '{}', expected something like 'B3/S23' or 'B3,12/S2,3'", rule);
+        let counts = |part: &str, prefix: char| {
+            let counts = part.strip_prefix(prefix).ok_or_else(invalid)?;
+            if !counts.contains(',') {
+                return counts.chars().map(|c| c.to_digit(10).map(|digit| digit as usize).ok_or_else(invalid)).collect();
+            }
+            counts.strip_suffix(',')
+                  .unwrap_or(counts)
+                  .split(',')
+                  .map(|count| match !count.is_empty() && count.chars().all(|c| c.is_ascii_digit()) {
+                      true => count.parse::<usize>().map_err(|_| invalid()),
+                      false => Err(invalid()),
+                  })
+                  .collect::<Result<Vec<usize>, String>>()
+        };
+        match rule.split_once('/') {
+            Some((birth, survival)) => Ok(Rule { birth: counts(birth, 'B')?, survival: counts(survival, 'S')? }),
+            None => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let counts = |counts: &[usize]| match counts {
+            [count] if *count > 9 => format!("{},", count),
+            counts if counts.iter().any(|&count| count > 9) => {
+                counts.iter().map(|count| count.to_string()).collect::<Vec<String>>().join(",")
+            },
+            counts => counts.iter().map(|count| count.to_string()).collect::<String>(),
+        };
+        write!(f, "B{}/S{}", counts(&self.birth), counts(&self.survival))
+    }
+}
+
+/// How a run of `settle` ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// Nothing changes from `generation` on.
+    Stable { generation: usize },
+    /// The state at `start` comes back every `period` generations.
+    Cycle { start: usize, period: usize },
+    /// Still changing when the generation limit was reached.
+    Unsettled,
+}
+
+pub struct Automaton<T: Topology> {
+    topology: T,
+    rule: Rule,
+    live: HashSet<T::Cell>,
+    generation: usize,
+}
+
+impl<T: Topology> Automaton<T> {
+    /// Panics if `rule` brings lonely cells to life on a topology with no end
+    /// of cells.
+    pub fn new(topology: T, rule: Rule, live: impl IntoIterator<Item = T::Cell>) -> Automaton<T> {
+        assert!(
+            !rule.birth.contains(&0) || topology.cells().is_some(),
+            "{} needs a finite topology", rule
+        );
+        Automaton { topology, rule, live: live.into_iter().collect(), generation: 0 }
+    }
+
+    pub fn live(&self) -> &HashSet<T::Cell> {
+        &self.live
+    }
+
+    pub fn population(&self) -> usize {
+        self.live.len()
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Moves on one generation, returns whether anything changed.
+    pub fn step(&mut self) -> bool {
+        let next = self.next(&self.live);
+        let changed = next != self.live;
+        self.live = next;
+        self.generation += 1;
+        changed
+    }
+
+    // the generation after `live`
+    fn next(&self, live: &HashSet<T::Cell>) -> HashSet<T::Cell> {
+        let mut counts: HashMap<T::Cell, usize> = HashMap::new();
+        for cell in live {
+            counts.entry(*cell).or_insert(0);
+            self.topology.neighbours(cell, |neighbour| *counts.entry(neighbour).or_insert(0) += 1);
+        }
+        if self.rule.birth.contains(&0) {
+            for cell in self.topology.cells().unwrap_or_default() {
+                counts.entry(cell).or_insert(0);
+            }
+        }
+
+        counts.into_iter()
+            .filter(|(cell, n)| self.rule.lives(live.contains(cell), *n))
+            .map(|(cell, _)| cell)
+            .collect()
+    }
+
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Steps until the cells stop changing or repeat an earlier state, for at
+    /// most `limit` generations. Only a hash of each state is kept, when one
+    /// comes round again the earlier state is worked out afresh from the first
+    /// to make sure it really is the same.
+    pub fn settle(&mut self, limit: usize) -> Outcome {
+        let (first, from) = (self.live.clone(), self.generation);
+        let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+        for _ in 0..limit {
+            seen.entry(self.fingerprint()).or_default().push(self.generation);
+            if !self.step() {
+                return Outcome::Stable { generation: self.generation - 1 };
+            }
+            for &start in seen.get(&self.fingerprint()).into_iter().flatten() {
+                let earlier = (from..start).fold(first.clone(), |live, _| self.next(&live));
+                if earlier == self.live {
+                    return Outcome::Cycle { start, period: self.generation - start };
+                }
+            }
+        }
+        Outcome::Unsettled
+    }
+
+    // the same for the same cells whatever order the set keeps them in
+    fn fingerprint(&self) -> u64 {
+        self.live.iter().fold(0, |fingerprint: u64, cell| {
+            let mut hasher = DefaultHasher::new();
+            cell.hash(&mut hasher);
+            fingerprint.wrapping_add(hasher.finish())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn neighbours<T: Topology>(topology: &T, cell: T::Cell) -> Vec<T::Cell> {
+        let mut neighbours = Vec::new();
+        topology.neighbours(&cell, |neighbour| neighbours.push(neighbour));
+        neighbours.sort();
+        neighbours
+    }
+
+    #[test]
+    fn topology_tests() {
+        let moore = Lattice::<2>::new(Neighbourhood::Moore);
+        assert_eq!(neighbours(&moore, [0, 0]), vec![[-1, -1], [-1, 0], [-1, 1], [0, -1], [0, 1], [1, -1], [1, 0], [1, 1]]);
+        let von_neumann = Lattice::<2>::new(Neighbourhood::VonNeumann);
+        assert_eq!(neighbours(&von_neumann, [5, -5]), vec![[4, -5], [5, -6], [5, -4], [6, -5]]);
+        assert_eq!(neighbours(&Lattice::<3>::new(Neighbourhood::Moore), [0; 3]).len(), 26);
+        assert_eq!(neighbours(&Lattice::<3>::new(Neighbourhood::VonNeumann), [0; 3]).len(), 6);
+        assert_eq!(neighbours(&Lattice::<4>::new(Neighbourhood::Moore), [0; 4]).len(), 80);
+        assert_eq!(Lattice::<2>::new(Neighbourhood::Moore).cells(), None);
+
+        let hex = neighbours(&Hex, (1, -1, 0));
+        assert_eq!(hex.len(), 6);
+        assert!(hex.iter().all(|&(x, y, z)| x + y + z == 0), "{:?}", hex);
+        assert!(!hex.contains(&(1, -1, 0)));
+        assert_eq!(Hex.cells(), None);
+
+        let graph = Graph::new(vec![vec![1, 2], vec![0], vec![]]);
+        assert_eq!(neighbours(&graph, 0), vec![1, 2]);
+        assert_eq!(neighbours(&graph, 2), vec![]);
+        assert_eq!(graph.cells(), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn settle_tests() {
+        let life = || Rule::new(&[3], &[2, 3]);
+        let plane = || Lattice::<2>::new(Neighbourhood::Moore);
+
+        let mut blinker = Automaton::new(plane(), life(), vec![[0, -1], [0, 0], [0, 1]]);
+        assert_eq!(blinker.settle(100), Outcome::Cycle { start: 0, period: 2 });
+        assert_eq!(blinker.generation(), 2);
+        // starting part way through counts from there
+        let mut blinker = Automaton::new(plane(), life(), vec![[0, -1], [0, 0], [0, 1]]);
+        blinker.run(3);
+        assert_eq!(blinker.settle(100), Outcome::Cycle { start: 3, period: 2 });
+
+        let mut block = Automaton::new(plane(), life(), vec![[0, 0], [0, 1], [1, 0], [1, 1]]);
+        assert_eq!(block.settle(100), Outcome::Stable { generation: 0 });
+        assert_eq!(block.population(), 4);
+        // a lone cell dies, and nothing stays nothing
+        let mut lonely = Automaton::new(plane(), life(), vec![[0, 0]]);
+        assert_eq!(lonely.settle(100), Outcome::Stable { generation: 1 });
+
+        // a glider never comes back to where it was
+        let mut glider = Automaton::new(plane(), life(), vec![[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]]);
+        assert_eq!(glider.settle(40), Outcome::Unsettled);
+        assert_eq!((glider.generation(), glider.population()), (40, 5));
+        assert_eq!(Automaton::new(plane(), life(), vec![[0, 0]]).settle(0), Outcome::Unsettled);
+
+        // with a finite topology empty cells can be born with no neighbours
+        let mut flashing = Automaton::new(Graph::new(vec![vec![]; 3]), Rule::new(&[0], &[]), vec![]);
+        assert_eq!(flashing.settle(100), Outcome::Cycle { start: 0, period: 2 });
+    }
+
+    #[test]
+    #[should_panic(expected = "B0/S23 needs a finite topology")]
+    fn birth_from_nothing_test() {
+        Automaton::new(Lattice::<2>::new(Neighbourhood::Moore), Rule::new(&[0], &[2, 3]), vec![]);
+    }
+
+    #[test]
+    fn example_tests() {
+        // the pocket dimension from 2020 day 17
+        let active = [[1, 0, 0], [2, 1, 0], [0, 2, 0], [1, 2, 0], [2, 2, 0]];
+        let mut pocket = Automaton::new(Lattice::<3>::new(Neighbourhood::Moore), Rule::new(&[3], &[2, 3]), active);
+        pocket.run(6);
+        assert_eq!((pocket.generation(), pocket.population()), (6, 112));
+
+        // the lobby floor from 2020 day 24
+        let paths = "sesenwnenenewseeswwswswwnenewsewsw neeenesenwnwwswnenewnwwsewnenwseswesw seswneswswsenwwnwse \
+                     nwnwneseeswswnenewneswwnewseswneseene swweswneswnenwsewnwneneseenw eesenwseswswnenwswnwnwsewwnwsene \
+                     sewnenenenesenwsewnenwwwse wenwwweseeeweswwwnwwe wsweesenenewnwwnwsenewsenwwsesesenwne \
+                     neeswseenwwswnwswswnw nenwswwsewswnenenewsenwsenwnesesenew enewnwewneswsewnwswenweswnenwsenwsw \
+                     sweneswneswneneenwnewenewwneswswnese swwesenesewenwneswnwwneseswwne enesenwswwswneneswsenwnewswseenwsese \
+                     wnwnesenesenenwwnenwsewesewsesesew nenewswnwewswnenesenwnesewesw eneswnwswnwsenenwnwnwwseeswneewsenese \
+                     neswnwewnwnwseenwseesewsenwsweewe wseweeenwnesenwwwswnew";
+        let directions = [("e", (1, -1, 0)), ("w", (-1, 1, 0)), ("ne", (1, 0, -1)), ("sw", (-1, 0, 1)), ("nw", (0, 1, -1)), ("se", (0, -1, 1))];
+        let mut black = HashSet::new();
+        for path in paths.split_whitespace() {
+            let (mut tile, mut rest) = ((0, 0, 0), path);
+            while !rest.is_empty() {
+                let (name, (dx, dy, dz)) = directions.iter().find(|(name, _)| rest.starts_with(name)).unwrap();
+                tile = (tile.0 + dx, tile.1 + dy, tile.2 + dz);
+                rest = &rest[name.len()..];
+            }
+            if !black.remove(&tile) {
+                black.insert(tile);
+            }
+        }
+        let mut floor = Automaton::new(Hex, Rule::new(&[2], &[1, 2]), black);
+        assert_eq!(floor.population(), 10);
+        floor.step();
+        assert_eq!(floor.population(), 15);
+        floor.run(9);
+        assert_eq!((floor.generation(), floor.population()), (10, 37));
+    }
+
+    #[test]
+    fn rule_tests() {
+        let life = "B3/S23".parse::<Rule>().unwrap();
+        assert_eq!(life, Rule::new(&[3], &[2, 3]));
+        assert_eq!(life.to_string(), "B3/S23");
+        assert_eq!("B/S".parse::<Rule>(), Ok(Rule::new(&[], &[])));
+
+        let big = "B10,12/S2,3".parse::<Rule>().unwrap();
+        assert_eq!(big, Rule::new(&[10, 12], &[2, 3]));
+        assert_eq!(big.to_string(), "B10,12/S23");
+        let single = "B10,/S23".parse::<Rule>().unwrap();
+        assert_eq!(single, Rule::new(&[10], &[2, 3]));
+        assert_eq!(single.to_string(), "B10,/S23");
+        assert_eq!("B1,0/S23".parse::<Rule>(), Ok(Rule::new(&[1, 0], &[2, 3])));
+
+        for invalid in ["B3S23", "B3/23", "Bx/S23", "B3,,4/S2", "B,/S2", "B3,+4/S2"] {
+            assert_eq!(invalid.parse::<Rule>(),
+                       Err(format!("invalid rule '{}', expected something like 'B3/S23' or 'B3,12/S2,3'", invalid)));
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use std::fmt;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::ParseError;
+
+/// Every direction, clockwise from up.
+pub const ALL_DIRECTIONS: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular grid of cells stored row by row. Positions are `(x, y)` with
+/// `(0, 0)` in the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Panics if there aren't `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), width * height, "a {}x{} grid needs {} cells", width, height, width * height);
+        Grid { width, height, cells }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Grid<T> {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(x, y));
+            }
+        }
+        Grid { width, height, cells }
+    }
+
+    /// Panics if the rows aren't all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let width = rows.first().map_or(0, |row| row.len());
+        let height = rows.len();
+        assert!(rows.iter().all(|row| row.len() == width), "every row needs {} cells", width);
+        Grid { width, height, cells: rows.into_iter().flatten().collect() }
+    }
+
+    /// Parses one row per line of `input`. `cell` turns each character into a
+    /// cell, rejected characters are reported as `expected` and rows longer
+    /// than the first as `end_of_row`.
+    pub fn parse<E: Clone>(
+        input: &str,
+        cell: impl Fn(char) -> Option<T>,
+        expected: E,
+        end_of_row: E,
+    ) -> Result<Grid<T>, ParseError<E>> {
+        let rows = input.trim_end().lines().collect::<Vec<&str>>();
+        if rows.is_empty() {
+            return Err(ParseError::at(input, &input[input.len()..], expected));
+        }
+        Grid::parse_rows(input, &rows, cell, expected, end_of_row)
+    }
+
+    /// Like `parse`, for rows that have already been split out of `input`.
+    pub fn parse_rows<E: Clone>(
+        input: &str,
+        rows: &[&str],
+        cell: impl Fn(char) -> Option<T>,
+        expected: E,
+        end_of_row: E,
+    ) -> Result<Grid<T>, ParseError<E>> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        for row in rows {
+            let start = cells.len();
+            for (i, c) in row.char_indices() {
+                if width == Some(cells.len() - start) {
+                    return Err(ParseError::at(input, &row[i..], end_of_row));
+                }
+                match cell(c) {
+                    Some(value) => cells.push(value),
+                    None => return Err(ParseError::at(input, &row[i..], expected)),
+                }
+            }
+            match width {
+                Some(width) if cells.len() - start < width => {
+                    return Err(ParseError::at(input, &row[row.len()..], expected));
+                },
+                Some(_) => {},
+                None => width = Some(cells.len()),
+            }
+        }
+        Ok(Grid { width: width.unwrap_or(0), height: rows.len(), cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        match x < self.width && y < self.height {
+            true => Some(&self.cells[y * self.width + x]),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        match x < self.width && y < self.height {
+            true => Some(&mut self.cells[y * self.width + x]),
+            false => None,
+        }
+    }
+
+    /// Treats the grid as repeating forever in every direction.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self.cells[y * self.width + x]
+    }
+
+    /// The position one step from `(x, y)` in `direction`, if it's on the grid.
+    pub fn step(&self, x: usize, y: usize, direction: (isize, isize)) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(direction.0)?;
+        let y = y.checked_add_signed(direction.1)?;
+        match x < self.width && y < self.height {
+            true => Some((x, y)),
+            false => None,
+        }
+    }
+
+    /// The positions one step away from `(x, y)` in each of `directions`,
+    /// leaving out any that fall off the grid.
+    pub fn neighbours<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        directions: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        directions.iter().filter_map(move |direction| self.step(x, y, *direction))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // a zero width grid would make chunks panic
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    /// Turned a quarter turn clockwise.
+    pub fn rotate(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |x, y| self[(y, self.height - 1 - x)].clone())
+    }
+
+    /// Mirrored left to right.
+    pub fn flip(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |x, y| self[(self.width - 1 - x, y)].clone())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside a {}x{} grid", x, y, self.width, self.height);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside a {}x{} grid", x, y, self.width, self.height);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+// one line per row, so a grid of chars prints the way it was parsed
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Expected {
+        Cell,
+        EndOfRow,
+    }
+
+    fn letters(input: &str) -> Result<Grid<char>, ParseError<Expected>> {
+        Grid::parse(input, |c| Some(c).filter(char::is_ascii_lowercase), Expected::Cell, Expected::EndOfRow)
+    }
+
+    #[test]
+    fn parse_tests() {
+        let grid = letters("abc\ndef\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid, Grid::new(3, 2, "abcdef".chars().collect()));
+        assert_eq!(grid, Grid::from_rows(vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]));
+        assert_eq!(grid, Grid::from_fn(3, 2, |x, y| (b'a' + (y * 3 + x) as u8) as char));
+        assert_eq!(grid.to_string(), "abc\ndef");
+        assert_eq!(letters(&grid.to_string()), Ok(grid.clone()));
+        assert_eq!(letters("abc\r\ndef\r\n"), Ok(grid));
+
+        let err = letters("abc\nd#f").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected), (2, 2, Expected::Cell));
+        let err = letters("abc\ndefg").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected), (2, 4, Expected::EndOfRow));
+        let err = letters("abc\nde").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected, err.found), (2, 3, Expected::Cell, String::new()));
+        assert_eq!(letters("\n").unwrap_err().expected, Expected::Cell);
+    }
+
+    #[test]
+    fn index_tests() {
+        let mut grid = letters("abc\ndef").unwrap();
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get(2, 1), Some(&'f'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+        *grid.get_mut(0, 1).unwrap() = 'x';
+        grid[(1, 1)] = 'y';
+        assert_eq!(grid.row(1), ['x', 'y', 'f']);
+        assert_eq!(grid.get_mut(3, 1), None);
+
+        assert_eq!(*grid.get_wrapping(3, 0), 'a');
+        assert_eq!(*grid.get_wrapping(-1, -1), 'f');
+        assert_eq!(*grid.get_wrapping(-4, 5), 'f');
+        assert_eq!(grid.column(1).collect::<String>(), "by");
+        assert_eq!(grid.cells().last(), Some(((2, 1), &'f')));
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nXYF");
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) is outside a 3x2 grid")]
+    fn index_panic_test() {
+        let _ = letters("abc\ndef").unwrap()[(3, 0)];
+    }
+
+    #[test]
+    fn step_tests() {
+        let grid = Grid::filled(3, 2, '.');
+        assert_eq!(grid.step(1, 0, (1, 1)), Some((2, 1)));
+        assert_eq!(grid.step(0, 0, (-1, 0)), None);
+        assert_eq!(grid.step(0, 0, (0, -1)), None);
+        assert_eq!(grid.step(2, 1, (1, 0)), None);
+        assert_eq!(grid.step(2, 1, (0, 1)), None);
+        assert_eq!(grid.neighbours(0, 0, &ALL_DIRECTIONS).collect::<Vec<_>>(), vec![(1, 0), (1, 1), (0, 1)]);
+        assert_eq!(grid.neighbours(1, 1, &ALL_DIRECTIONS).count(), 5);
+    }
+
+    #[test]
+    fn transform_tests() {
+        let grid = letters("abc\ndef").unwrap();
+        assert_eq!(grid.rotate().to_string(), "da\neb\nfc");
+        assert_eq!(grid.flip().to_string(), "cba\nfed");
+        assert_eq!(grid.rotate().rotate().rotate().rotate(), grid);
+        assert_eq!(grid.flip().flip(), grid);
+        // turning then flipping is flipping then turning the other way
+        assert_eq!(grid.rotate().flip(), grid.flip().rotate().rotate().rotate());
+
+        let empty = Grid::<char>::filled(0, 0, '.');
+        assert_eq!(empty.rotate(), empty);
+        assert_eq!(empty.rows().count(), 0);
+    }
+}
@@ -0,0 +1,162 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::io;
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+use std::process;
+
+pub mod automaton;
+pub mod grid;
+
+pub use grid::Grid;
+
+pub struct Answers {
+    pub part_1: String,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    pub fn new(part_1: impl Display, part_2: impl Display) -> Answers {
+        Answers {
+            part_1: part_1.to_string(),
+            part_2: Some(part_2.to_string()),
+        }
+    }
+}
+
+pub fn read_file(filename: &str) -> String {
+    let mut file = std::fs::File::open(filename).expect("Failed to open file");
+    let mut contents = String::new();
+    file.read_to_string(&mut contents).expect("Failed to read file");
+
+    contents
+}
+
+/// A parse failure in a day's puzzle input. `E` is the day's own list of tokens
+/// its parser can expect.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError<E> {
+    pub line: usize,
+    pub column: usize,
+    pub expected: E,
+    pub found: String,
+}
+
+impl<E> ParseError<E> {
+    /// `token` has to be a slice of `input`, its offset gives the line and column.
+    pub fn at(input: &str, token: &str, expected: E) -> ParseError<E> {
+        let offset = (token.as_ptr() as usize).saturating_sub(input.as_ptr() as usize);
+        let before = input.get(..offset).unwrap_or(input);
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected,
+            found: token.lines().next().unwrap_or("").to_owned(),
+        }
+    }
+}
+
+impl<E: Display> Display for ParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: expected {}, ", self.line, self.column, self.expected)?;
+        match self.found.is_empty() {
+            true => write!(f, "found nothing"),
+            false => write!(f, "found '{}'", self.found),
+        }
+    }
+}
+
+impl<E: fmt::Debug + Display> Error for ParseError<E> {}
+
+/// Unwraps a day's result, or prints the error and exits the binary.
+pub fn or_exit<T, E: Display>(result: Result<T, E>) -> T {
+    match result {
+        Ok(val) => val,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Where a day's puzzle input comes from.
+pub enum Input {
+    Stdin,
+    File(PathBuf),
+    Inline(String),
+}
+
+impl Input {
+    /// `-` reads stdin and anything else is a path, so a file that isn't there
+    /// is reported when it's read rather than parsed as the puzzle input.
+    /// Inline input has to be asked for with `Input::Inline`.
+    pub fn from_arg(arg: &str) -> Input {
+        match arg {
+            "-" => Input::Stdin,
+            path => Input::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Input::Stdin => {
+                let mut contents = String::new();
+                io::stdin().read_to_string(&mut contents)?;
+                Ok(contents)
+            },
+            Input::File(path) => std::fs::read_to_string(path),
+            Input::Inline(value) => Ok(value.clone()),
+        }
+    }
+
+    /// Reads the input a line at a time instead of all at once.
+    pub fn reader(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Input::Stdin => Ok(Box::new(BufReader::new(io::stdin()))),
+            Input::File(path) => Ok(Box::new(BufReader::new(std::fs::File::open(path)?))),
+            Input::Inline(value) => Ok(Box::new(io::Cursor::new(value.clone().into_bytes()))),
+        }
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Input::Stdin => write!(f, "stdin"),
+            Input::File(path) => write!(f, "{}", path.display()),
+            Input::Inline(_) => write!(f, "inline input"),
+        }
+    }
+}
+
+/// Reads the input named by the first command line argument, falling back to
+/// `input.txt` in the current directory.
+pub fn read_input() -> String {
+    read_or_exit(&day_args().0)
+}
+
+/// Splits a day's command line into the input named by the first argument,
+/// `input.txt` if there isn't one, and any `--options` that follow it.
+pub fn day_args() -> (Input, Vec<String>) {
+    let mut args = env::args().skip(1).peekable();
+    let input = match args.peek() {
+        Some(arg) if !arg.starts_with("--") => Input::from_arg(&args.next().unwrap()),
+        _ => Input::File(PathBuf::from("input.txt")),
+    };
+    (input, args.collect())
+}
+
+/// Reads `input`, or prints why it couldn't and exits the binary.
+pub fn read_or_exit(input: &Input) -> String {
+    match input.read() {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("failed to read {}: {}", input, err);
+            process::exit(1);
+        }
+    }
+}
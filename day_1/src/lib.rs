@@ -0,0 +1,214 @@
+use std::fmt;
+
+use common::Answers;
+
+const TARGET: i64 = 2020;
+
+/// A way of picking entries that add up to the target: their positions in the
+/// original slice, in order, and their values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Combination {
+    pub indices: Vec<usize>,
+    pub values: Vec<i64>,
+}
+
+impl Combination {
+    pub fn product(&self) -> i64 {
+        self.values.iter().product()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Expense,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Expense => write!(f, "a positive whole number"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Vec<i64>;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    input.split(|c: char| c == ',' || c.is_whitespace())
+         .filter(|expense| !expense.is_empty())
+         .map(|expense| {
+             expense.parse::<u32>()
+                    .map(i64::from)
+                    .map_err(|_| ParseError::at(input, expense, Expected::Expense))
+         })
+         .collect::<Result<Vec<i64>, ParseError>>()
+}
+
+pub fn solve(input: &str) -> Result<Answers, String> {
+    let expenses = parse(input).map_err(|err| err.to_string())?;
+    Ok(Answers::new(part_1(&expenses)?, part_2(&expenses)?))
+}
+
+pub fn part_1(expenses: &[i64]) -> Result<i64, String> {
+    k_sum(expenses, TARGET, 2).first().map(Combination::product).ok_or_else(|| format!("no pair adds up to {}", TARGET))
+}
+
+pub fn part_2(expenses: &[i64]) -> Result<i64, String> {
+    k_sum(expenses, TARGET, 3).first().map(Combination::product).ok_or_else(|| format!("no triple adds up to {}", TARGET))
+}
+
+/// Every way of picking `k` entries of `values` that add up to `target`, ordered
+/// by their indices. Entries with the same value at different indices count as
+/// different picks.
+pub fn k_sum(values: &[i64], target: i64, k: usize) -> Vec<Combination> {
+    let mut sorted = values.iter().copied().zip(0..).collect::<Vec<(i64, usize)>>();
+    sorted.sort_unstable();
+    let search = Search {
+        prefix: sorted.iter().scan(0, |sum, (value, _)| { *sum += *value as i128; Some(*sum) }).collect(),
+        sorted,
+    };
+
+    let mut found = Vec::new();
+    search.find(0, k, target as i128, &mut Vec::new(), &mut found);
+
+    let mut combinations = found.into_iter()
+        .map(|mut positions| {
+            positions.sort_unstable_by_key(|p| search.sorted[*p].1);
+            Combination {
+                indices: positions.iter().map(|p| search.sorted[*p].1).collect(),
+                values: positions.iter().map(|p| search.sorted[*p].0).collect(),
+            }
+        })
+        .collect::<Vec<Combination>>();
+    combinations.sort_unstable_by(|a, b| a.indices.cmp(&b.indices));
+    combinations
+}
+
+// entries sorted by value along with their original index, and running totals so
+// the smallest and largest sums of any run of them are cheap to find
+struct Search {
+    sorted: Vec<(i64, usize)>,
+    prefix: Vec<i128>,
+}
+
+impl Search {
+    fn sum(&self, from: usize, to: usize) -> i128 {
+        let before = if from == 0 { 0 } else { self.prefix[from - 1] };
+        match to > from {
+            true => self.prefix[to - 1] - before,
+            false => 0,
+        }
+    }
+
+    fn value(&self, position: usize) -> i128 {
+        self.sorted[position].0 as i128
+    }
+
+    // picks `k` more entries from `start` on, `chosen` are positions in `sorted`
+    fn find(&self, start: usize, k: usize, target: i128, chosen: &mut Vec<usize>, found: &mut Vec<Vec<usize>>) {
+        let len = self.sorted.len();
+        if len - start < k {
+            return;
+        }
+        match k {
+            0 => {
+                if target == 0 {
+                    found.push(chosen.clone());
+                }
+            },
+            1 => {
+                let first = start + self.sorted[start..].partition_point(|(value, _)| (*value as i128) < target);
+                for position in first..len {
+                    if self.value(position) != target {
+                        break;
+                    }
+                    found.push([chosen.as_slice(), &[position]].concat());
+                }
+            },
+            2 => self.find_pairs(start, target, chosen, found),
+            _ => {
+                for position in start..=len - k {
+                    // the rest only get bigger from here
+                    if self.sum(position, position + k) > target {
+                        break;
+                    }
+                    if self.value(position) + self.sum(len - (k - 1), len) < target {
+                        continue;
+                    }
+                    chosen.push(position);
+                    self.find(position + 1, k - 1, target - self.value(position), chosen, found);
+                    chosen.pop();
+                }
+            },
+        }
+    }
+
+    // closes in from both ends, every run of equal values pairs up with the other end's run
+    fn find_pairs(&self, start: usize, target: i128, chosen: &[usize], found: &mut Vec<Vec<usize>>) {
+        let mut low = start;
+        // nothing past what the smallest entry leaves of the target can be part of a pair
+        let most = target - self.value(low);
+        let mut high = match start + self.sorted[start..].partition_point(|(value, _)| (*value as i128) <= most) {
+            end if end > start => end - 1,
+            _ => return,
+        };
+        while low < high {
+            let sum = self.value(low) + self.value(high);
+            if sum < target {
+                low += 1;
+            } else if sum > target {
+                high -= 1;
+            } else if self.value(low) == self.value(high) {
+                for a in low..high {
+                    for b in a + 1..=high {
+                        found.push([chosen, &[a, b]].concat());
+                    }
+                }
+                return;
+            } else {
+                let low_end = (low..high).find(|p| self.value(*p) != self.value(low)).unwrap_or(high);
+                let high_start = (low_end..=high).rev().find(|p| self.value(*p) != self.value(high)).map_or(low_end, |p| p + 1);
+                for a in low..low_end {
+                    for b in high_start..=high {
+                        found.push([chosen, &[a, b]].concat());
+                    }
+                }
+                low = low_end;
+                high = high_start - 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn k_sum_tests() {
+        let expenses = [1721, 979, 366, 299, 675, 1456];
+        assert_eq!(part_1(&expenses), Ok(514579));
+        assert_eq!(part_2(&expenses), Ok(241861950));
+        assert_eq!(part_1(&[1, 2, 3]), Err("no pair adds up to 2020".to_owned()));
+        assert_eq!(part_2(&[2000, 20]), Err("no triple adds up to 2020".to_owned()));
+        assert_eq!(k_sum(&expenses, 2020, 2), vec![Combination { indices: vec![0, 3], values: vec![1721, 299] }]);
+        assert_eq!(k_sum(&expenses, 2020, 3), vec![Combination { indices: vec![1, 2, 4], values: vec![979, 366, 675] }]);
+
+        // the same value at different indices is a different pick
+        let ones = k_sum(&[1, 1, 1], 2, 2);
+        assert_eq!(ones.iter().map(|c| c.indices.clone()).collect::<Vec<Vec<usize>>>(), vec![vec![0, 1], vec![0, 2], vec![1, 2]]);
+
+        let values = [5, -2, 3, 0, 4, 1];
+        assert_eq!(k_sum(&values, 3, 1).len(), 1);
+        assert_eq!(k_sum(&values, 0, 0).len(), 1);
+        assert_eq!(k_sum(&values, 8, 4), vec![
+            Combination { indices: vec![0, 1, 4, 5], values: vec![5, -2, 4, 1] },
+            Combination { indices: vec![2, 3, 4, 5], values: vec![3, 0, 4, 1] },
+        ]);
+        assert_eq!(k_sum(&values, 11, 6).len(), 1);
+        assert!(k_sum(&values, 11, 7).is_empty());
+        assert!(k_sum(&[], 0, 2).is_empty());
+    }
+}
@@ -0,0 +1,354 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+use common::Answers;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Joltage,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Joltage => write!(f, "a positive whole number"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Vec<u32>;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    let mut adaptors = input.trim()
+                          .split("\n")
+                          .map(|number| number.parse::<u32>().map_err(|_| ParseError::at(input, number, Expected::Joltage)))
+                          .collect::<Result<Vec<u32>, ParseError>>()?;
+
+    adaptors.sort();
+    Ok(adaptors)
+}
+
+pub fn solve(input: &str) -> Result<Answers, String> {
+    let adaptors = parse(input).map_err(|err| err.to_string())?;
+    Ok(Answers::new(part_1(&adaptors)?, part_2(&adaptors)))
+}
+
+pub fn part_1(adaptors: &[u32]) -> Result<usize, String> {
+    let chain = Adapters::new(adaptors, Gaps::standard());
+    if let Some((from, to)) = chain.unusable() {
+        return Err(format!("the adapters can't all be used: {} jolts from {} to {}", to - from, from, to));
+    }
+    let differences = chain.differences();
+    let count = |gap| differences.get(&gap).copied().unwrap_or(0);
+    Ok(count(1) * count(3))
+}
+
+pub fn part_2(adaptors: &[u32]) -> BigUint {
+    Adapters::new(adaptors, Gaps::standard()).arrangements()
+}
+
+/// The differences in joltage an adapter can take, smallest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gaps(Vec<u32>);
+
+impl Gaps {
+    /// The puzzle's adapters: anything from 1 to 3 jolts lower.
+    pub fn standard() -> Gaps {
+        Gaps(vec![1, 2, 3])
+    }
+
+    /// None if there are no gaps or one of them is 0.
+    pub fn new(mut gaps: Vec<u32>) -> Option<Gaps> {
+        gaps.sort_unstable();
+        gaps.dedup();
+        match gaps.first() {
+            Some(&smallest) if smallest > 0 => Some(Gaps(gaps)),
+            _ => None,
+        }
+    }
+
+    /// Reads gaps written like `1,2,3`.
+    pub fn parse(gaps: &str) -> Option<Gaps> {
+        Gaps::new(gaps.split(',').map(|gap| gap.trim().parse().ok()).collect::<Option<Vec<u32>>>()?)
+    }
+
+    pub fn contains(&self, gap: u32) -> bool {
+        self.0.binary_search(&gap).is_ok()
+    }
+
+    pub fn largest(&self) -> u32 {
+        *self.0.last().unwrap()
+    }
+}
+
+impl fmt::Display for Gaps {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let gaps = self.0.iter().map(|gap| gap.to_string()).collect::<Vec<String>>();
+        write!(f, "{{{}}}", gaps.join(","))
+    }
+}
+
+/// The outlet, the adapters and the device, in order of joltage. The device
+/// takes the largest gap above the highest adapter.
+pub struct Adapters {
+    jolts: Vec<u32>,
+    gaps: Gaps,
+    // how many ways each joltage can be chained on to the device
+    ways: Vec<BigUint>,
+}
+
+impl Adapters {
+    pub fn new(adaptors: &[u32], gaps: Gaps) -> Adapters {
+        let mut jolts = Vec::with_capacity(adaptors.len() + 2);
+        jolts.push(0);
+        jolts.extend_from_slice(adaptors);
+        jolts[1..].sort_unstable();
+        jolts.push(jolts[jolts.len() - 1] + gaps.largest());
+
+        let mut ways = vec![BigUint::zero(); jolts.len()];
+        ways[jolts.len() - 1] = BigUint::one();
+        for from in (0..jolts.len() - 1).rev() {
+            let mut total = BigUint::zero();
+            for to in reachable(&jolts, &gaps, from, from) {
+                total += &ways[to];
+            }
+            ways[from] = total;
+        }
+        Adapters { jolts, gaps, ways }
+    }
+
+    /// The outlet and device's joltages along with the adapters'.
+    pub fn jolts(&self) -> &[u32] {
+        &self.jolts
+    }
+
+    pub fn device(&self) -> u32 {
+        self.jolts[self.jolts.len() - 1]
+    }
+
+    /// How often each difference turns up when every adapter is used.
+    pub fn differences(&self) -> BTreeMap<u32, usize> {
+        let mut differences = BTreeMap::new();
+        for pair in self.jolts.windows(2) {
+            *differences.entry(pair[1] - pair[0]).or_insert(0) += 1;
+        }
+        differences
+    }
+
+    /// The first two joltages in a row that are too far apart, or too close,
+    /// for every adapter to be used.
+    pub fn unusable(&self) -> Option<(u32, u32)> {
+        self.jolts.windows(2)
+                  .find(|pair| !self.gaps.contains(pair[1] - pair[0]))
+                  .map(|pair| (pair[0], pair[1]))
+    }
+
+    /// How many different chains go from the outlet to the device.
+    pub fn arrangements(&self) -> BigUint {
+        self.ways[0].clone()
+    }
+
+    /// Every chain from the outlet to the device, in order.
+    pub fn chains(&self) -> Chains<'_> {
+        Chains { adapters: self, path: Vec::new(), done: self.ways[0].is_zero() }
+    }
+
+    /// A chain picked at random, each as likely as any other. None if there
+    /// aren't any.
+    pub fn sample(&self, seed: u64) -> Option<Vec<u32>> {
+        if self.ways[0].is_zero() {
+            return None;
+        }
+        let mut seed = seed;
+        let mut chain = vec![self.jolts[0]];
+        let mut at = 0;
+        while at < self.jolts.len() - 1 {
+            // a number below the chains from here picks one of them, so
+            // each next step is weighted by how many chains go through it
+            let digits = (0..self.ways[at].bits() / 32 + 3).map(|_| {
+                                                               seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                                                               (seed >> 32) as u32
+                                                           })
+                                                           .collect();
+            let mut pick = BigUint::new(digits) % &self.ways[at];
+            for to in self.next(at, at) {
+                if pick < self.ways[to] {
+                    at = to;
+                    break;
+                }
+                pick -= &self.ways[to];
+            }
+            chain.push(self.jolts[at]);
+        }
+        Some(chain)
+    }
+
+    // the joltages after `after` that `from` can be chained to and that lead
+    // on to the device
+    fn next(&self, from: usize, after: usize) -> impl Iterator<Item = usize> + '_ {
+        reachable(&self.jolts, &self.gaps, from, after).filter(move |&to| !self.ways[to].is_zero())
+    }
+}
+
+fn reachable<'a>(jolts: &'a [u32], gaps: &'a Gaps, from: usize, after: usize) -> impl Iterator<Item = usize> + 'a {
+    (after + 1..jolts.len()).take_while(move |&to| jolts[to] - jolts[from] <= gaps.largest())
+                            .filter(move |&to| gaps.contains(jolts[to] - jolts[from]))
+}
+
+/// Walks every chain of adapters without holding more than one at a time,
+/// see `Adapters::chains`.
+pub struct Chains<'a> {
+    adapters: &'a Adapters,
+    // which joltages the last chain went through
+    path: Vec<usize>,
+    done: bool,
+}
+
+impl<'a> Chains<'a> {
+    // follows the first way on from the end of the path to the device
+    fn descend(&mut self) {
+        let end = self.adapters.jolts.len() - 1;
+        while let Some(&at) = self.path.last() {
+            if at == end {
+                break;
+            }
+            self.path.push(self.adapters.next(at, at).next().unwrap());
+        }
+    }
+
+    fn current(&self) -> Vec<u32> {
+        self.path.iter().map(|&at| self.adapters.jolts[at]).collect()
+    }
+}
+
+impl<'a> Iterator for Chains<'a> {
+    type Item = Vec<u32>;
+
+    fn next(&mut self) -> Option<Vec<u32>> {
+        if self.done {
+            return None;
+        }
+        if self.path.is_empty() {
+            self.path.push(0);
+            self.descend();
+            return Some(self.current());
+        }
+        loop {
+            let last = self.path.pop().unwrap();
+            let from = match self.path.last() {
+                Some(&from) => from,
+                None => {
+                    self.done = true;
+                    return None;
+                },
+            };
+            if let Some(to) = self.adapters.next(from, last).next() {
+                self.path.push(to);
+                self.descend();
+                return Some(self.current());
+            }
+        }
+    }
+}
+
+/// Renders a histogram of differences, one line per gap.
+pub fn render_differences(differences: &BTreeMap<u32, usize>, width: usize) -> String {
+    let most = differences.values().copied().max().unwrap_or(0).max(1);
+    differences.iter()
+               .map(|(gap, &count)| format!("{:>3} {:<width$} {}", gap, "#".repeat(count * width / most), count, width = width))
+               .collect::<Vec<String>>()
+               .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL: &str = "16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4";
+
+    const LARGER: &str = "28\n33\n18\n42\n31\n14\n46\n20\n48\n47\n24\n23\n49\n45\n19\n38\n39\n11\n1\n32\n25\n35\n8\n17\n7\n9\n4\n2\n34\n10\n3";
+
+    #[test]
+    fn part_1_tests() {
+        assert_eq!(part_1(&parse(SMALL).unwrap()), Ok(35));
+        assert_eq!(part_1(&parse(LARGER).unwrap()), Ok(220));
+        let differences = Adapters::new(&parse(LARGER).unwrap(), Gaps::standard()).differences();
+        assert_eq!(differences.into_iter().collect::<Vec<(u32, usize)>>(), vec![(1, 22), (3, 10)]);
+        assert_eq!(part_1(&[1, 2, 6]), Err("the adapters can't all be used: 4 jolts from 2 to 6".to_owned()));
+    }
+
+    #[test]
+    fn part_2_tests() {
+        assert_eq!(part_2(&parse(SMALL).unwrap()), BigUint::from(8u32));
+        assert_eq!(part_2(&parse(LARGER).unwrap()), BigUint::from(19208u32));
+        assert_eq!(part_2(&[1, 2, 6]), BigUint::zero());
+
+        // one adapter per joltage chains like the tribonacci numbers, well past a u64
+        let adaptors = (1..=200).collect::<Vec<u32>>();
+        let counts = (197..=200).map(|top| part_2(&adaptors[..top])).collect::<Vec<BigUint>>();
+        assert_eq!(counts[3], &counts[0] + &counts[1] + &counts[2]);
+        assert!(counts[3] > BigUint::from(u64::MAX));
+    }
+
+    #[test]
+    fn gap_tests() {
+        assert_eq!(Gaps::parse("3, 1,2,1"), Some(Gaps::standard()));
+        assert_eq!(Gaps::parse("0,1"), None);
+        assert_eq!(Gaps::parse(""), None);
+        assert_eq!(Gaps::parse("1,5").unwrap().to_string(), "{1,5}");
+
+        let adaptors = [1, 2, 6, 7, 11, 12];
+        let chain = Adapters::new(&adaptors, Gaps::parse("1,5").unwrap());
+        assert_eq!(chain.device(), 17);
+        assert_eq!(chain.unusable(), Some((2, 6)));
+        assert_eq!(chain.chains().collect::<Vec<Vec<u32>>>(), vec![vec![0, 1, 2, 7, 12, 17], vec![0, 1, 6, 7, 12, 17], vec![0, 1, 6, 11, 12, 17]]);
+        assert_eq!(chain.arrangements(), BigUint::from(3u32));
+
+        // the chains and their count agree with trying every set of adapters
+        let mut seed: u64 = 10;
+        let mut random = |below: u64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) % below
+        };
+        for _ in 0..200 {
+            let gaps = Gaps::new((0..1 + random(3)).map(|_| 1 + random(5) as u32).collect()).unwrap();
+            let mut adaptors = (0..random(12)).map(|_| 1 + random(20) as u32).collect::<Vec<u32>>();
+            adaptors.sort_unstable();
+            let chain = Adapters::new(&adaptors, gaps.clone());
+            let mut brute = (0..1u32 << adaptors.len()).map(|used| {
+                                                           let mut jolts = vec![0];
+                                                           jolts.extend((0..adaptors.len()).filter(|&i| used >> i & 1 == 1).map(|i| adaptors[i]));
+                                                           jolts.push(chain.device());
+                                                           jolts
+                                                       })
+                                                       .filter(|jolts| jolts.windows(2).all(|pair| gaps.contains(pair[1] - pair[0])))
+                                                       .collect::<Vec<Vec<u32>>>();
+            brute.sort();
+            let chains = chain.chains().collect::<Vec<Vec<u32>>>();
+            assert_eq!(BigUint::from(chains.len()), chain.arrangements());
+            assert_eq!(chains.len(), brute.len());
+            let mut sorted = chains.clone();
+            sorted.sort();
+            assert_eq!(sorted, brute);
+        }
+    }
+
+    #[test]
+    fn sample_tests() {
+        let chain = Adapters::new(&parse(SMALL).unwrap(), Gaps::standard());
+        let chains = chain.chains().collect::<Vec<Vec<u32>>>();
+        let mut seen = vec![0; chains.len()];
+        for seed in 0..400 {
+            let sample = chain.sample(seed).unwrap();
+            seen[chains.iter().position(|chain| *chain == sample).unwrap()] += 1;
+        }
+        assert!(seen.iter().all(|&count| count > 20), "{:?}", seen);
+        assert_eq!(Adapters::new(&[1, 2, 6], Gaps::standard()).sample(0), None);
+        assert_eq!(Adapters::new(&[1, 2, 6], Gaps::standard()).chains().next(), None);
+    }
+}
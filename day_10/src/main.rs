@@ -1,83 +1,66 @@
-use std::io::Read;
+use std::process;
 
-#[derive(Clone)]
-struct Node {
-    jolts: u32,
-    value: u64
-}
-
-fn main() {
-    let contents = read_file("input.txt");
-    let mut adaptors = contents.trim()
-                          .split("\n")
-                          .map(|number| number.parse::<u32>().unwrap())
-                          .collect::<Vec<u32>>();
+use common::{day_args, or_exit, read_or_exit};
+use day_10::{parse, part_1, part_2, render_differences, Adapters, Gaps};
 
-    adaptors.sort();
-    let part_1_result = part_1(&adaptors);
-    println!("part 1: {}", part_1_result);
+const USAGE: &str = "usage: day_10 [input] [--gaps <a,b,..>] [--histogram] [--chains <n>] [--sample <n>] [--seed <n>]";
 
-    let part_2_result = part_2(&adaptors);
-    println!("part 2: {}", part_2_result);
+fn main() {
+    let (input, options) = day_args();
+    let adaptors = or_exit(parse(&read_or_exit(&input)));
 
-}
+    if options.is_empty() {
+        println!("part 1: {}", or_exit(part_1(&adaptors)));
+        println!("part 2: {}", part_2(&adaptors));
+        return;
+    }
 
-fn part_1(adaptors: &Vec<u32>) -> u32 {
-    let mut ones: u32 = 0 + adaptors[0];
-    let mut threes: u32 = 1;
-    for i in 0..adaptors.len() - 1 {
-        if adaptors[i + 1] - adaptors[i] > 1 {
-            ones += 1;
-        } else {
-            threes += 1;
+    let mut gaps = Gaps::standard();
+    let mut histogram = false;
+    let mut chains = 0;
+    let mut samples = 0;
+    let mut seed = 10;
+    let mut options = options.iter().map(|option| option.as_str());
+    while let Some(option) = options.next() {
+        match option {
+            "--gaps" => gaps = options.next().and_then(Gaps::parse).unwrap_or_else(|| usage()),
+            "--histogram" => histogram = true,
+            "--chains" => chains = number(options.next()) as usize,
+            "--sample" => samples = number(options.next()),
+            "--seed" => seed = number(options.next()),
+            _ => usage(),
         }
+    }
 
+    let adapters = Adapters::new(&adaptors, gaps.clone());
+    println!("gaps {}, device at {} jolts", gaps, adapters.device());
+    if let Some((from, to)) = adapters.unusable() {
+        println!("the adapters can't all be used: {} jolts from {} to {}", to - from, from, to);
+    }
+    if histogram {
+        println!("{}", render_differences(&adapters.differences(), 50));
+    }
+    println!("{} arrangements", adapters.arrangements());
+    for chain in adapters.chains().take(chains) {
+        println!("{}", join(&chain));
+    }
+    for sample in 0..samples {
+        match adapters.sample(seed.wrapping_add(sample)) {
+            Some(chain) => println!("{}", join(&chain)),
+            None => break,
+        }
     }
-    ones * threes
 }
 
-fn part_2(adaptors: &Vec<u32>) -> u64 {
-    let mut adaptors: Vec<Node> = adaptors.iter()
-                                          .map(|adaptor| Node{
-                                              jolts: *adaptor,
-                                              value: 0
-                                          })
-                                          .collect();
-
-    adaptors.push(Node {
-        jolts: adaptors[adaptors.len() - 1].jolts + 3,
-        value: 0
-    });
-    adaptors.reverse();
-
-    let head = Node {
-        jolts: 0,
-        value: 1
-    };
-
-    walk_and_count(head, Vec::new(), adaptors)
+fn join(chain: &[u32]) -> String {
+    chain.iter().map(|jolts| jolts.to_string()).collect::<Vec<String>>().join(" ")
 }
 
-fn walk_and_count(mut head: Node, mut tail: Vec<Node>, mut adaptors: Vec<Node>) -> u64 {
-    for child in &tail {
-        if head.jolts - child.jolts < 4 {
-            head.value += child.value;
-        } else {
-            break;
-        }
-    }
-    if adaptors.len() == 0 {
-        return head.value;
-    }
-    tail.insert(0, head);
-    head = adaptors.pop().unwrap();
-    return walk_and_count(head, tail, adaptors);
+fn number(option: Option<&str>) -> u64 {
+    option.and_then(|number| number.parse().ok()).unwrap_or_else(|| usage())
 }
 
-fn read_file(filename: &str) -> String {
-    let mut file = std::fs::File::open(filename).expect("Failed to open file");
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).expect("Failed to read file");
-
-    contents
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
 }
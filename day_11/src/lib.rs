@@ -0,0 +1,594 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use common::automaton::{Automaton, Graph, Outcome, Rule};
+use common::grid::ALL_DIRECTIONS;
+use common::{Answers, Grid};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Chair {
+    Empty,
+    Occupied,
+    Floor
+}
+
+impl fmt::Display for Chair {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Chair::Empty => write!(f, "L"),
+            Chair::Occupied => write!(f, "#"),
+            Chair::Floor => write!(f, "."),
+        }
+    }
+}
+
+pub type FloorPlan = Grid<Chair>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Seat,
+    EndOfRow,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Seat => write!(f, "'L', '#' or '.'"),
+            Expected::EndOfRow => write!(f, "the end of the row"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = FloorPlan;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    let chair = |c| match c {
+        'L' => Some(Chair::Empty),
+        '#' => Some(Chair::Occupied),
+        '.' => Some(Chair::Floor),
+        _ => None,
+    };
+    Grid::parse(input, chair, Expected::Seat, Expected::EndOfRow)
+}
+
+pub fn solve(input: &str) -> Result<Answers, String> {
+    let floor_plan = parse(input).map_err(|err| err.to_string())?;
+    Ok(Answers::new(part_1(&floor_plan)?, part_2(&floor_plan)?))
+}
+
+pub fn part_1(floor_plan: &FloorPlan) -> Result<usize, String> {
+    occupied(Seating::PART_1.packed(floor_plan).settle(usize::MAX))
+}
+
+pub fn part_2(floor_plan: &FloorPlan) -> Result<usize, String> {
+    occupied(Seating::PART_2.packed(floor_plan).settle(usize::MAX))
+}
+
+fn occupied(ending: Ending) -> Result<usize, String> {
+    match ending {
+        Ending::Settled { occupied, .. } => Ok(occupied),
+        ending => Err(format!("the seating never settles, it {}", ending)),
+    }
+}
+
+/// Which seats someone pays attention to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Neighbourhood {
+    /// The eight seats around them.
+    Adjacent,
+    /// The first seat they can see in each of the eight directions, floor
+    /// doesn't block the view.
+    LineOfSight,
+    /// Every seat up to this many steps away in any direction.
+    Radius(usize),
+}
+
+/// How people choose their seats: they sit down when at most `sit` of their
+/// neighbours are occupied and get up when at least `leave` are.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Seating {
+    pub neighbourhood: Neighbourhood,
+    /// Whether looking off one edge of the plan carries on from the other.
+    pub wrap: bool,
+    pub sit: usize,
+    pub leave: usize,
+}
+
+impl Seating {
+    pub const PART_1: Seating = Seating { neighbourhood: Neighbourhood::Adjacent, wrap: false, sit: 0, leave: 4 };
+    pub const PART_2: Seating = Seating { neighbourhood: Neighbourhood::LineOfSight, wrap: false, sit: 0, leave: 5 };
+
+    /// The seats someone sitting at `(x, y)` pays attention to, each once.
+    pub fn neighbours(&self, floor_plan: &FloorPlan, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let is_seat = |position: &(usize, usize)| floor_plan[*position] != Chair::Floor;
+        let mut neighbours = match self.neighbourhood {
+            Neighbourhood::Adjacent => ALL_DIRECTIONS.iter()
+                                                     .filter_map(|&direction| self.step(floor_plan, (x, y), direction))
+                                                     .filter(is_seat)
+                                                     .collect(),
+            Neighbourhood::LineOfSight => ALL_DIRECTIONS.iter()
+                                                        .filter_map(|&direction| self.ray(floor_plan, x, y, direction).find(is_seat))
+                                                        .collect(),
+            Neighbourhood::Radius(radius) => {
+                let radius = radius as isize;
+                (-radius..=radius).flat_map(|dy| (-radius..=radius).map(move |dx| (dx, dy)))
+                                  .filter(|&direction| direction != (0, 0))
+                                  .filter_map(|direction| self.step(floor_plan, (x, y), direction))
+                                  .filter(is_seat)
+                                  .collect::<Vec<(usize, usize)>>()
+            },
+        };
+        // wrapping round a small plan can come back to the same seat, or this one
+        neighbours.retain(|&position| position != (x, y));
+        neighbours.sort_unstable();
+        neighbours.dedup();
+        neighbours
+    }
+
+    fn step(&self, floor_plan: &FloorPlan, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        if !self.wrap {
+            return floor_plan.step(x, y, (dx, dy));
+        }
+        let x = (x as isize + dx).rem_euclid(floor_plan.width() as isize) as usize;
+        let y = (y as isize + dy).rem_euclid(floor_plan.height() as isize) as usize;
+        Some((x, y))
+    }
+
+    // every position from (x, y) in `direction`, stopping at the edge or, when
+    // wrapping, once it comes back round to (x, y)
+    fn ray<'a>(&'a self, floor_plan: &'a FloorPlan, x: usize, y: usize, direction: (isize, isize)) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let mut position = (x, y);
+        std::iter::from_fn(move || {
+                             position = self.step(floor_plan, position, direction)?;
+                             Some(position)
+                         })
+        .take_while(move |&position| position != (x, y))
+    }
+
+    fn rule(&self) -> Rule {
+        Rule::new(&(0..=self.sit).collect::<Vec<usize>>(), &(0..self.leave).collect::<Vec<usize>>())
+    }
+
+    // every seat numbered row by row, with the numbers of its neighbours
+    fn seats(&self, floor_plan: &FloorPlan) -> (Vec<(usize, usize)>, Vec<Vec<usize>>) {
+        let seats = floor_plan.cells()
+                              .filter(|(_, chair)| **chair != Chair::Floor)
+                              .map(|(position, _)| position)
+                              .collect::<Vec<(usize, usize)>>();
+        let index = seats.iter()
+                         .enumerate()
+                         .map(|(i, position)| (*position, i))
+                         .collect::<HashMap<(usize, usize), usize>>();
+        let neighbours = seats.iter()
+                              .map(|&(x, y)| self.neighbours(floor_plan, x, y).iter().map(|seat| index[seat]).collect())
+                              .collect();
+        (seats, neighbours)
+    }
+
+    /// Seats people on `floor_plan` following these rules, one generation at
+    /// a time.
+    pub fn simulate(&self, floor_plan: &FloorPlan) -> Simulation {
+        let (seats, neighbours) = self.seats(floor_plan);
+        let occupied = seats.iter()
+                            .enumerate()
+                            .filter(|(_, seat)| floor_plan[**seat] == Chair::Occupied)
+                            .map(|(i, _)| i);
+
+        let automaton = Automaton::new(Graph::new(neighbours), self.rule(), occupied);
+        Simulation { floor_plan: empty(floor_plan), seats, automaton }
+    }
+
+    /// The same as `simulate`, laid out for big floor plans.
+    pub fn packed(&self, floor_plan: &FloorPlan) -> Packed {
+        let (seats, neighbours) = self.seats(floor_plan);
+        let mut watchers = vec![Vec::new(); seats.len()];
+        for (seat, neighbours) in neighbours.iter().enumerate() {
+            for &neighbour in neighbours {
+                watchers[neighbour].push(seat as u32);
+            }
+        }
+        let neighbours = Adjacency::new(neighbours.into_iter().map(|neighbours| neighbours.into_iter().map(|seat| seat as u32).collect()).collect());
+
+        let mut current = vec![0u64; seats.len().div_ceil(64)];
+        for (i, seat) in seats.iter().enumerate() {
+            if floor_plan[*seat] == Chair::Occupied {
+                current[i / 64] |= 1 << (i % 64);
+            }
+        }
+        let occupied = current.iter().map(|word| word.count_ones() as usize).sum();
+        let keys = (0..seats.len() as u64).map(mix).collect::<Vec<u64>>();
+        let hash = (0..seats.len()).filter(|&i| current[i / 64] >> (i % 64) & 1 == 1).fold(0, |hash, i| hash ^ keys[i]);
+        Packed {
+            floor_plan: empty(floor_plan),
+            neighbours,
+            watchers: Adjacency::new(watchers),
+            sit: self.sit,
+            leave: self.leave,
+            next: current.clone(),
+            current,
+            dirty: (0..seats.len() as u32).collect(),
+            queued: vec![false; seats.len()],
+            changed: Vec::new(),
+            seats,
+            keys,
+            hash,
+            generation: 0,
+            occupied,
+        }
+    }
+}
+
+// splitmix64's finaliser, spreads consecutive numbers all over the 64 bits
+fn mix(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+fn empty(floor_plan: &FloorPlan) -> FloorPlan {
+    floor_plan.map(|chair| if *chair == Chair::Floor { Chair::Floor } else { Chair::Empty })
+}
+
+/// How the seating ended up.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ending {
+    /// Nobody moves from `generation` on.
+    Settled { generation: usize, occupied: usize },
+    /// The seating at `start` comes back every `period` generations.
+    Oscillating { start: usize, period: usize },
+    /// People were still moving when the generation limit was reached.
+    Unsettled { generation: usize },
+}
+
+impl fmt::Display for Ending {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Ending::Settled { generation, occupied } => write!(f, "settles at generation {} with {} seats occupied", generation, occupied),
+            Ending::Oscillating { start, period } => write!(f, "repeats every {} generations from generation {}", period, start),
+            Ending::Unsettled { generation } => write!(f, "still changing at generation {}", generation),
+        }
+    }
+}
+
+/// People choosing seats, see `Seating::simulate`.
+pub struct Simulation {
+    // the plan with every seat empty
+    floor_plan: FloorPlan,
+    seats: Vec<(usize, usize)>,
+    automaton: Automaton<Graph>,
+}
+
+impl Simulation {
+    pub fn generation(&self) -> usize {
+        self.automaton.generation()
+    }
+
+    pub fn occupied(&self) -> usize {
+        self.automaton.population()
+    }
+
+    /// The seating as it is now.
+    pub fn floor_plan(&self) -> FloorPlan {
+        let mut floor_plan = self.floor_plan.clone();
+        for &seat in self.automaton.live() {
+            floor_plan[self.seats[seat]] = Chair::Occupied;
+        }
+        floor_plan
+    }
+
+    /// Moves on one generation, returns whether anyone moved.
+    pub fn step(&mut self) -> bool {
+        self.automaton.step()
+    }
+
+    /// Runs until nobody moves or the seating repeats, for at most `limit`
+    /// generations.
+    pub fn settle(&mut self, limit: usize) -> Ending {
+        match self.automaton.settle(limit) {
+            Outcome::Stable { generation } => Ending::Settled { generation, occupied: self.occupied() },
+            Outcome::Cycle { start, period } => Ending::Oscillating { start, period },
+            Outcome::Unsettled => Ending::Unsettled { generation: self.generation() },
+        }
+    }
+
+    /// The seating at each generation from this one on, ending with the first
+    /// one nobody moves from. Never ends if the seating oscillates.
+    pub fn generations(&mut self) -> impl Iterator<Item = FloorPlan> + '_ {
+        let mut settled = false;
+        std::iter::from_fn(move || {
+            if settled {
+                return None;
+            }
+            let floor_plan = self.floor_plan();
+            settled = !self.step();
+            Some(floor_plan)
+        })
+    }
+}
+
+// every seat's list of seats, one after another in a single buffer
+struct Adjacency {
+    // seat i's list is seats[starts[i]..starts[i + 1]]
+    starts: Vec<u32>,
+    seats: Vec<u32>,
+}
+
+impl Adjacency {
+    fn new(lists: Vec<Vec<u32>>) -> Adjacency {
+        let mut starts = Vec::with_capacity(lists.len() + 1);
+        starts.push(0);
+        let mut seats = Vec::new();
+        for list in lists {
+            seats.extend(list);
+            starts.push(seats.len() as u32);
+        }
+        Adjacency { starts, seats }
+    }
+
+    fn of(&self, seat: u32) -> &[u32] {
+        &self.seats[self.starts[seat as usize] as usize..self.starts[seat as usize + 1] as usize]
+    }
+}
+
+/// People choosing seats like `Simulation`, built for big floor plans, see
+/// `Seating::packed`.
+///
+/// Each seat's neighbours are worked out once. Who's sitting where is kept a
+/// bit per seat in two buffers, the next generation is written into the
+/// spare one and then they swap. Only seats that saw someone move last
+/// generation are looked at again, nobody else can have a reason to. The
+/// seating's hash is kept up to date from the seats that change, so spotting
+/// a repeat doesn't mean hashing every seat each generation.
+pub struct Packed {
+    floor_plan: FloorPlan,
+    seats: Vec<(usize, usize)>,
+    neighbours: Adjacency,
+    // the seats that have each seat as a neighbour
+    watchers: Adjacency,
+    sit: usize,
+    leave: usize,
+    current: Vec<u64>,
+    next: Vec<u64>,
+    dirty: Vec<u32>,
+    queued: Vec<bool>,
+    changed: Vec<u32>,
+    // a random key per seat, the hash is the xor of the occupied seats' keys
+    keys: Vec<u64>,
+    hash: u64,
+    generation: usize,
+    occupied: usize,
+}
+
+impl Packed {
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn occupied(&self) -> usize {
+        self.occupied
+    }
+
+    fn is_occupied(&self, seat: u32) -> bool {
+        self.current[seat as usize / 64] >> (seat % 64) & 1 == 1
+    }
+
+    /// The seating as it is now.
+    pub fn floor_plan(&self) -> FloorPlan {
+        let mut floor_plan = self.floor_plan.clone();
+        for (i, seat) in self.seats.iter().enumerate() {
+            if self.is_occupied(i as u32) {
+                floor_plan[*seat] = Chair::Occupied;
+            }
+        }
+        floor_plan
+    }
+
+    /// Moves on one generation, returns whether anyone moved.
+    pub fn step(&mut self) -> bool {
+        self.changed.clear();
+        let dirty = std::mem::take(&mut self.dirty);
+        for &seat in &dirty {
+            self.queued[seat as usize] = false;
+            let occupied = self.is_occupied(seat);
+            // counting stops as soon as the answer is known
+            let enough = if occupied { self.leave } else { self.sit + 1 };
+            let mut count = 0;
+            for &neighbour in self.neighbours.of(seat) {
+                if self.is_occupied(neighbour) {
+                    count += 1;
+                    if count == enough {
+                        break;
+                    }
+                }
+            }
+            if (count < enough) != occupied {
+                self.changed.push(seat);
+            }
+        }
+
+        // the spare buffer matches this generation, so only the seats that
+        // changed need writing, before and after the swap
+        for &seat in &self.changed {
+            self.next[seat as usize / 64] ^= 1 << (seat % 64);
+        }
+        std::mem::swap(&mut self.current, &mut self.next);
+        for &seat in &self.changed {
+            self.next[seat as usize / 64] ^= 1 << (seat % 64);
+        }
+
+        self.dirty = dirty;
+        self.dirty.clear();
+        for &seat in &self.changed {
+            self.hash ^= self.keys[seat as usize];
+            match self.is_occupied(seat) {
+                true => self.occupied += 1,
+                false => self.occupied -= 1,
+            }
+            for &watcher in std::iter::once(&seat).chain(self.watchers.of(seat)) {
+                if !self.queued[watcher as usize] {
+                    self.queued[watcher as usize] = true;
+                    self.dirty.push(watcher);
+                }
+            }
+        }
+        self.generation += 1;
+        !self.changed.is_empty()
+    }
+
+    /// Runs until nobody moves or the seating repeats, for at most `limit`
+    /// generations.
+    pub fn settle(&mut self, limit: usize) -> Ending {
+        let first = self.generation;
+        let mut seen: HashMap<u64, usize> = HashMap::new();
+        // the seats that changed going into each generation after `first`, to
+        // check a repeated hash really is a repeated seating
+        let mut changes: Vec<Vec<u32>> = Vec::new();
+        for _ in 0..limit {
+            seen.insert(self.hash, self.generation);
+            if !self.step() {
+                return Ending::Settled { generation: self.generation - 1, occupied: self.occupied };
+            }
+            changes.push(self.changed.clone());
+            if let Some(&start) = seen.get(&self.hash) {
+                if self.flips_back(&changes[start - first..]) {
+                    return Ending::Oscillating { start, period: self.generation - start };
+                }
+            }
+        }
+        Ending::Unsettled { generation: self.generation }
+    }
+
+    // whether every seat changed an even number of times, leaving the seating
+    // as it was before them
+    fn flips_back(&self, changes: &[Vec<u32>]) -> bool {
+        let mut flipped = vec![false; self.seats.len()];
+        for &seat in changes.iter().flatten() {
+            flipped[seat as usize] = !flipped[seat as usize];
+        }
+        !flipped.contains(&true)
+    }
+}
+
+/// A `width` by `height` plan of empty seats with about one spot in
+/// `floor` left as floor, the same plan for the same `seed`.
+pub fn generate(width: usize, height: usize, floor: u64, seed: u64) -> FloorPlan {
+    let mut seed = seed;
+    Grid::from_fn(width, height, |_, _| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        match (seed >> 33) % floor.max(1) {
+            0 => Chair::Floor,
+            _ => Chair::Empty,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test_input.txt");
+
+    #[test]
+    fn part_1_test() {
+        let floor_plan = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&floor_plan), Ok(37));
+        assert_eq!(Seating::PART_1.simulate(&floor_plan).settle(usize::MAX), Ending::Settled { generation: 5, occupied: 37 });
+    }
+
+    #[test]
+    fn part_2_test() {
+        let floor_plan = parse(EXAMPLE).unwrap();
+
+        let result = part_2(&floor_plan);
+        assert_eq!(result, Ok(26));
+    }
+
+    #[test]
+    fn seating_tests() {
+        let plan = parse("L.L\n...\nL.L").unwrap();
+        let adjacent = Seating::PART_1;
+        let wrapping = Seating { wrap: true, ..adjacent };
+        assert_eq!(adjacent.neighbours(&plan, 0, 0), vec![]);
+        assert_eq!(wrapping.neighbours(&plan, 0, 0), vec![(0, 2), (2, 0), (2, 2)]);
+        let radius = Seating { neighbourhood: Neighbourhood::Radius(2), ..adjacent };
+        assert_eq!(radius.neighbours(&plan, 0, 0), vec![(0, 2), (2, 0), (2, 2)]);
+        assert_eq!(radius.neighbours(&parse("LLLLL").unwrap(), 0, 0), vec![(1, 0), (2, 0)]);
+
+        let row = parse(".L.L").unwrap();
+        let sight = Seating::PART_2;
+        assert_eq!(sight.neighbours(&row, 1, 0), vec![(3, 0)]);
+        assert_eq!(Seating { wrap: true, ..sight }.neighbours(&row, 1, 0), vec![(3, 0)]);
+        assert_eq!(Seating { wrap: true, ..sight }.neighbours(&parse("L...").unwrap(), 0, 0), vec![]);
+        assert_eq!(sight.neighbours(&parse("L...L").unwrap(), 0, 0), vec![(4, 0)]);
+
+        // everyone leaves as soon as anyone is next to them, so the pair stand up
+        // and sit down together for ever
+        let restless = Seating { leave: 1, ..adjacent };
+        assert_eq!(restless.simulate(&parse("LL").unwrap()).settle(100), Ending::Oscillating { start: 0, period: 2 });
+        assert_eq!(restless.simulate(&parse("LL").unwrap()).settle(1), Ending::Unsettled { generation: 1 });
+        assert_eq!(part_1(&parse("LL").unwrap()), Ok(2));
+        assert_eq!(occupied(Ending::Oscillating { start: 0, period: 2 }),
+                   Err("the seating never settles, it repeats every 2 generations from generation 0".to_owned()));
+
+        // nobody minds company with a high enough `sit`
+        let sociable = Seating { sit: 8, leave: 9, ..adjacent };
+        assert_eq!(sociable.simulate(&parse("LLL\nLLL").unwrap()).settle(100), Ending::Settled { generation: 1, occupied: 6 });
+    }
+
+    #[test]
+    fn generation_tests() {
+        let floor_plan = parse(EXAMPLE).unwrap();
+        let mut simulation = Seating::PART_1.simulate(&floor_plan);
+        let generations = simulation.generations().collect::<Vec<FloorPlan>>();
+        assert_eq!(generations.len(), 6);
+        assert_eq!(generations[0], floor_plan);
+        assert_eq!(generations[1].to_string(), floor_plan.to_string().replace('L', "#"));
+        assert_eq!(generations[5].iter().filter(|chair| **chair == Chair::Occupied).count(), 37);
+        assert_eq!(simulation.generation(), 6);
+
+        let mut restless = Seating { leave: 1, ..Seating::PART_1 }.simulate(&parse("LL").unwrap());
+        let plans = restless.generations().take(4).map(|plan| plan.to_string()).collect::<Vec<String>>();
+        assert_eq!(plans, vec!["LL", "##", "LL", "##"]);
+    }
+
+    #[test]
+    fn packed_tests() {
+        let restless = Seating { leave: 1, ..Seating::PART_1 };
+        let seatings = [
+            Seating::PART_1,
+            Seating::PART_2,
+            Seating { wrap: true, ..Seating::PART_2 },
+            Seating { neighbourhood: Neighbourhood::Radius(2), wrap: true, sit: 2, leave: 9 },
+            restless,
+        ];
+        for (i, seating) in seatings.iter().enumerate() {
+            for seed in 0..5 {
+                let floor_plan = generate(3 + seed as usize * 4, 2 + seed as usize * 3, 2 + seed % 4, seed + i as u64 * 10);
+                let mut simulation = seating.simulate(&floor_plan);
+                let mut packed = seating.packed(&floor_plan);
+                for _ in 0..3 {
+                    assert_eq!(packed.step(), simulation.step());
+                    assert_eq!(packed.floor_plan(), simulation.floor_plan());
+                    assert_eq!(packed.occupied(), simulation.occupied());
+                }
+                assert_eq!(packed.settle(1000), simulation.settle(1000));
+                assert_eq!(packed.floor_plan(), simulation.floor_plan());
+            }
+        }
+
+        let floor_plan = parse(EXAMPLE).unwrap();
+        assert_eq!(Seating::PART_1.packed(&floor_plan).settle(usize::MAX), Ending::Settled { generation: 5, occupied: 37 });
+        assert_eq!(Seating::PART_2.packed(&floor_plan).settle(usize::MAX), Ending::Settled { generation: 6, occupied: 26 });
+        assert_eq!(restless.packed(&parse("LL").unwrap()).settle(100), Ending::Oscillating { start: 0, period: 2 });
+        assert_eq!(restless.packed(&parse("LL").unwrap()).settle(1), Ending::Unsettled { generation: 1 });
+        // a hash seen before only counts once the seats are back as they were
+        let packed = restless.packed(&parse("LL").unwrap());
+        assert!(packed.flips_back(&[vec![0, 1], vec![1], vec![0]]));
+        assert!(!packed.flips_back(&[vec![0, 1], vec![1]]));
+        assert_eq!(generate(4, 3, 3, 7), generate(4, 3, 3, 7));
+        assert_eq!(generate(4, 3, 1, 7).to_string(), "....\n....\n....");
+    }
+}
@@ -0,0 +1,801 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Neg;
+
+use common::{Answers, Grid};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Action {
+    Turn,
+    Forward,
+    North,
+    East,
+    South,
+    West
+}
+
+type Direction = i32;
+const NORTH: Direction = 0;
+const EAST: Direction = 90;
+const SOUTH: Direction = 180;
+const WEST: Direction = 270;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instruction {
+    action: Action,
+    count: i32
+}
+
+// written the way it's parsed
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.action {
+            Action::Turn if self.count < 0 => write!(f, "L{}", -self.count),
+            Action::Turn => write!(f, "R{}", self.count),
+            Action::Forward => write!(f, "F{}", self.count),
+            Action::North => write!(f, "N{}", self.count),
+            Action::East => write!(f, "E{}", self.count),
+            Action::South => write!(f, "S{}", self.count),
+            Action::West => write!(f, "W{}", self.count),
+        }
+    }
+}
+
+/// A number of units to six decimal places. Whole numbers, and anything
+/// reached by turning through right angles, are exact. Arithmetic is checked,
+/// and never reaches `i64::MIN` so that every value can be negated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed(i64);
+
+impl Fixed {
+    pub const SCALE: i64 = 1_000_000;
+
+    pub fn from_f64(value: f64) -> Fixed {
+        Fixed((value * Fixed::SCALE as f64).round() as i64)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / Fixed::SCALE as f64
+    }
+
+    pub fn abs(self) -> Fixed {
+        Fixed(self.0.abs())
+    }
+
+    // `None` for anything that doesn't fit
+    fn checked(value: Option<i64>) -> Option<Fixed> {
+        value.filter(|&value| value != i64::MIN).map(Fixed)
+    }
+
+    fn checked_from_f64(value: f64) -> Option<Fixed> {
+        let value = (value * Fixed::SCALE as f64).round();
+        match value.abs() < i64::MAX as f64 {
+            true => Some(Fixed(value as i64)),
+            false => None,
+        }
+    }
+
+    pub fn checked_add(self, other: Fixed) -> Option<Fixed> {
+        Fixed::checked(self.0.checked_add(other.0))
+    }
+
+    pub fn checked_times(self, count: i32) -> Option<Fixed> {
+        Fixed::checked(self.0.checked_mul(count as i64))
+    }
+}
+
+impl From<i32> for Fixed {
+    fn from(value: i32) -> Fixed {
+        Fixed(value as i64 * Fixed::SCALE)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+
+    fn neg(self) -> Fixed {
+        Fixed(-self.0)
+    }
+}
+
+// whole numbers print without a fraction, anything else with as many places
+// as it needs
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let (whole, fraction) = (self.0.abs() / Fixed::SCALE, self.0.abs() % Fixed::SCALE);
+        match fraction {
+            0 => write!(f, "{}{}", sign, whole),
+            _ => write!(f, "{}{}.{}", sign, whole, format!("{:06}", fraction).trim_end_matches('0')),
+        }
+    }
+}
+
+/// East is `x`, north is `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Point {
+        Point { x: Fixed::from(x), y: Fixed::from(y) }
+    }
+
+    /// `None` when the distance is too far to count.
+    pub fn manhattan(&self) -> Option<Fixed> {
+        self.x.abs().checked_add(self.y.abs())
+    }
+
+    pub fn checked_add(self, other: Point) -> Option<Point> {
+        Some(Point { x: self.x.checked_add(other.x)?, y: self.y.checked_add(other.y)? })
+    }
+
+    pub fn checked_times(self, count: i32) -> Option<Point> {
+        Some(Point { x: self.x.checked_times(count)?, y: self.y.checked_times(count)? })
+    }
+
+    /// Turned `degrees` clockwise about the origin. Right angles are exact,
+    /// anything else is rounded to the nearest millionth, and `None` if
+    /// that doesn't fit.
+    pub fn rotate(self, degrees: i32) -> Option<Point> {
+        Some(match degrees.rem_euclid(360) {
+            0 => self,
+            90 => Point { x: self.y, y: -self.x },
+            180 => Point { x: -self.x, y: -self.y },
+            270 => Point { x: -self.y, y: self.x },
+            degrees => {
+                let (sin, cos) = (degrees as f64).to_radians().sin_cos();
+                let (x, y) = (self.x.to_f64(), self.y.to_f64());
+                Point { x: Fixed::checked_from_f64(x * cos + y * sin)?, y: Fixed::checked_from_f64(y * cos - x * sin)? }
+            },
+        })
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+// one unit along a heading, clockwise from north
+fn bearing(heading: Direction) -> Point {
+    Point::new(0, 1).rotate(heading).expect("a unit turned is still a unit")
+}
+
+/// How the instructions are read: steering the ship itself (part 1) or
+/// moving a waypoint the ship follows (part 2).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Heading,
+    Waypoint,
+}
+
+/// A ship following instructions one at a time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ferry {
+    pub mode: Mode,
+    pub ship: Point,
+    /// Degrees clockwise from north.
+    pub heading: Direction,
+    /// Relative to the ship.
+    pub waypoint: Point,
+}
+
+impl Ferry {
+    /// Facing east with the waypoint 10 east and 1 north.
+    pub fn new(mode: Mode) -> Ferry {
+        Ferry { mode, ship: Point::default(), heading: EAST, waypoint: Point::new(10, 1) }
+    }
+
+    /// Follows one instruction, or explains why it takes the ship or the
+    /// waypoint further than can be counted, leaving the ferry as it was.
+    pub fn execute(&mut self, instruction: &Instruction) -> Result<(), String> {
+        let count = instruction.count;
+        let too_far = |what: &str| format!("{} takes the {} too far to keep track of", instruction, what);
+        let direction = match instruction.action {
+            Action::North => NORTH,
+            Action::East => EAST,
+            Action::South => SOUTH,
+            Action::West => WEST,
+            Action::Turn => {
+                match self.mode {
+                    Mode::Heading => self.heading = (self.heading + count.rem_euclid(360)) % 360,
+                    Mode::Waypoint => self.waypoint = self.waypoint.rotate(count).ok_or_else(|| too_far("waypoint"))?,
+                }
+                return Ok(());
+            },
+            Action::Forward => {
+                let step = match self.mode {
+                    Mode::Heading => bearing(self.heading).checked_times(count),
+                    Mode::Waypoint => self.waypoint.checked_times(count),
+                };
+                self.ship = step.and_then(|step| self.ship.checked_add(step)).ok_or_else(|| too_far("ship"))?;
+                return Ok(());
+            },
+        };
+        let step = bearing(direction).checked_times(count);
+        match self.mode {
+            Mode::Heading => self.ship = step.and_then(|step| self.ship.checked_add(step)).ok_or_else(|| too_far("ship"))?,
+            Mode::Waypoint => self.waypoint = step.and_then(|step| self.waypoint.checked_add(step)).ok_or_else(|| too_far("waypoint"))?,
+        }
+        Ok(())
+    }
+
+    /// Follows the instructions in turn, stopping at the first one that
+    /// goes too far.
+    pub fn run<'a>(&mut self, instructions: impl IntoIterator<Item = &'a Instruction>) -> Result<(), String> {
+        for (line, instruction) in instructions.into_iter().enumerate() {
+            self.execute(instruction).map_err(|err| format!("line {}: {}", line + 1, err))?;
+        }
+        Ok(())
+    }
+
+    /// Runs the instructions, noting where the ship and waypoint are after
+    /// each one.
+    pub fn track<'a>(&mut self, instructions: impl IntoIterator<Item = &'a Instruction>) -> Result<Track, String> {
+        let mut fixes = vec![self.fix()?];
+        for (line, instruction) in instructions.into_iter().enumerate() {
+            self.execute(instruction).and_then(|_| self.fix().map(|fix| fixes.push(fix)))
+                .map_err(|err| format!("line {}: {}", line + 1, err))?;
+        }
+        Ok(Track { mode: self.mode, fixes })
+    }
+
+    fn fix(&self) -> Result<Fix, String> {
+        let waypoint = self.ship.checked_add(self.waypoint).ok_or("the waypoint is too far away to put on the map")?;
+        Ok(Fix { ship: self.ship, heading: self.heading, waypoint })
+    }
+}
+
+/// Where the ship was at one point on a `Track`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fix {
+    pub ship: Point,
+    pub heading: Direction,
+    /// Where the waypoint was on the map, rather than relative to the ship.
+    pub waypoint: Point,
+}
+
+/// The ship's route, starting from before the first instruction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Track {
+    pub mode: Mode,
+    pub fixes: Vec<Fix>,
+}
+
+impl Track {
+    // every point worth showing: the ship's route and, when it's steering by
+    // one, the waypoint's
+    fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        let waypoints = match self.mode {
+            Mode::Heading => &self.fixes[..0],
+            Mode::Waypoint => &self.fixes[..],
+        };
+        self.fixes.iter().map(|fix| fix.ship).chain(waypoints.iter().map(|fix| fix.waypoint))
+    }
+
+    fn bounds(&self) -> (Point, Point) {
+        self.points().fold((Point::default(), Point::default()), |(low, high), point| {
+            (Point { x: low.x.min(point.x), y: low.y.min(point.y) }, Point { x: high.x.max(point.x), y: high.y.max(point.y) })
+        })
+    }
+
+    /// The route as an SVG image with north up, the ship's course in black
+    /// and the waypoint's in grey.
+    pub fn to_svg(&self) -> String {
+        let (low, high) = self.bounds();
+        let (width, height) = (high.x.to_f64() - low.x.to_f64(), high.y.to_f64() - low.y.to_f64());
+        let span = width.max(height).max(1.0);
+        let margin = span / 20.0;
+        let line = |points: &mut dyn Iterator<Item = Point>, colour: &str, width: f64| {
+            let points = points.map(|point| format!("{},{}", point.x, -point.y)).collect::<Vec<String>>();
+            format!("  <polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>\n", points.join(" "), colour, width)
+        };
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+            low.x.to_f64() - margin,
+            -high.y.to_f64() - margin,
+            width + 2.0 * margin,
+            height + 2.0 * margin
+        );
+        if self.mode == Mode::Waypoint {
+            svg += &line(&mut self.fixes.iter().map(|fix| fix.waypoint), "grey", span / 400.0);
+        }
+        svg += &line(&mut self.fixes.iter().map(|fix| fix.ship), "black", span / 200.0);
+        let end = self.fixes[self.fixes.len() - 1].ship;
+        svg += &format!("  <circle cx=\"0\" cy=\"0\" r=\"{}\" fill=\"green\"/>\n", span / 100.0);
+        svg += &format!("  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"red\"/>\n", end.x, -end.y, span / 100.0);
+        svg + "</svg>\n"
+    }
+
+    /// The ship's route drawn in `width` by `height` characters with north up,
+    /// starting at `S` and ending at `E`.
+    pub fn plot(&self, width: usize, height: usize) -> String {
+        let (width, height) = (width.max(2), height.max(2));
+        let (low, high) = self.bounds();
+        let cell = |point: Point| {
+            let scale = |value: Fixed, low: Fixed, high: Fixed, size: usize| {
+                let span = (high.to_f64() - low.to_f64()).max(f64::MIN_POSITIVE);
+                ((value.to_f64() - low.to_f64()) / span * (size - 1) as f64).round() as isize
+            };
+            (scale(point.x, low.x, high.x, width), (height - 1) as isize - scale(point.y, low.y, high.y, height))
+        };
+
+        let mut grid = Grid::filled(width, height, ' ');
+        for pair in self.fixes.windows(2) {
+            let ((x0, y0), (x1, y1)) = (cell(pair[0].ship), cell(pair[1].ship));
+            let steps = (x1 - x0).abs().max((y1 - y0).abs()).max(1);
+            for step in 0..=steps {
+                let x = x0 + (x1 - x0) * step / steps;
+                let y = y0 + (y1 - y0) * step / steps;
+                grid[(x as usize, y as usize)] = '#';
+            }
+        }
+        let (x, y) = cell(self.fixes[0].ship);
+        grid[(x as usize, y as usize)] = 'S';
+        let (x, y) = cell(self.fixes[self.fixes.len() - 1].ship);
+        grid[(x as usize, y as usize)] = 'E';
+        grid.rows().map(|row| row.iter().collect::<String>().trim_end().to_owned()).collect::<Vec<String>>().join("\n")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Action,
+    Count,
+    CountInRange,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Action => write!(f, "one of 'N', 'E', 'S', 'W', 'L', 'R' or 'F'"),
+            Expected::Count => write!(f, "a positive whole number"),
+            Expected::CountInRange => write!(f, "a count no bigger than {}", i32::MAX),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Vec<Instruction>;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    input.trim()
+            .split("\n")
+            .map(|instruction| to_instruction(input, instruction))
+            .collect::<Result<Vec<Instruction>, ParseError>>()
+}
+
+pub fn solve(input: &str) -> Result<Answers, String> {
+    let instructions = parse(input).map_err(|err| err.to_string())?;
+    Ok(Answers::new(part_1(&instructions)?, part_2(&instructions)?))
+}
+
+pub fn part_1(instructions: &[Instruction]) -> Result<Fixed, String> {
+    distance(navigate(instructions, Mode::Heading)?)
+}
+
+pub fn part_2(instructions: &[Instruction]) -> Result<Fixed, String> {
+    distance(navigate(instructions, Mode::Waypoint)?)
+}
+
+fn distance(ferry: Ferry) -> Result<Fixed, String> {
+    ferry.ship.manhattan().ok_or_else(|| format!("the ship ends up at {}, too far away to count", ferry.ship))
+}
+
+/// Where the ferry ends up after following every instruction.
+pub fn navigate(instructions: &[Instruction], mode: Mode) -> Result<Ferry, String> {
+    let mut ferry = Ferry::new(mode);
+    ferry.run(instructions)?;
+    Ok(ferry)
+}
+
+// whole numbers of units east and north
+type Vector = (i64, i64);
+
+// an instruction before its count is checked to fit
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Shift(Vector),
+    Turn(i32),
+    Forward(i64),
+}
+
+/// The fewest instructions that take the ship steering by its own heading
+/// from the origin to `ship`.
+///
+/// Each instruction moves the ship along one axis at most, so it takes one
+/// for each axis the ship has to move along.
+pub fn synthesise_heading(ship: (i32, i32)) -> Result<Vec<Instruction>, String> {
+    let target = (ship.0 as i64, ship.1 as i64);
+    let ops = shift(target).into_iter().collect::<Vec<Op>>();
+    checked(&ops, Mode::Heading, ship, None)
+}
+
+/// The fewest instructions that leave a ship following its waypoint at
+/// `ship` with the waypoint at `waypoint`, relative to the ship.
+///
+/// Moving and turning the waypoint takes at most two instructions to get it
+/// anywhere, so the ship can always get there in five: move the waypoint to
+/// the target, go forward once and move it to where it should end up. Going
+/// forward twice in a row is the same as once, so anything shorter goes
+/// forward once, or twice with the waypoint moved in between, and each of
+/// those few shapes is solved exactly.
+pub fn synthesise_waypoint(ship: (i32, i32), waypoint: (i32, i32)) -> Result<Vec<Instruction>, String> {
+    let start = (10, 1);
+    let target = (ship.0 as i64, ship.1 as i64);
+    let end = (waypoint.0 as i64, waypoint.1 as i64);
+
+    let mut routes = Vec::new();
+    if target == (0, 0) {
+        routes.push(retarget(start, end));
+    }
+    // forward once, to a waypoint that divides the distance
+    for k in divisors(gcd(target.0.abs(), target.1.abs())) {
+        let via = (target.0 / k, target.1 / k);
+        routes.push([retarget(start, via), vec![Op::Forward(k)], retarget(via, end)].concat());
+    }
+    // forward twice, moving the waypoint to where it ends up in between
+    if start != end {
+        if let Some((k1, k2)) = split(target, start, end) {
+            routes.push([vec![Op::Forward(k1)], retarget(start, end), vec![Op::Forward(k2)]].concat());
+        }
+    }
+    routes.extend(bridges(start, end, target));
+
+    let shortest = routes.into_iter().min_by_key(|route| route.len()).unwrap_or_default();
+    checked(&shortest, Mode::Waypoint, ship, Some(waypoint))
+}
+
+// the waypoint turned `degrees` clockwise, for right angles
+fn turned((x, y): Vector, degrees: i32) -> Vector {
+    match degrees.rem_euclid(360) {
+        90 => (y, -x),
+        180 => (-x, -y),
+        270 => (-y, x),
+        _ => (x, y),
+    }
+}
+
+fn shift((x, y): Vector) -> Vec<Op> {
+    [(x, 0), (0, y)].iter().filter(|&&d| d != (0, 0)).map(|&d| Op::Shift(d)).collect()
+}
+
+// the fewest instructions moving the waypoint from `from` to `to`: none, a
+// turn or move along one axis, or a move along each axis
+fn retarget(from: Vector, to: Vector) -> Vec<Op> {
+    if from == to {
+        return Vec::new();
+    }
+    if let Some(&degrees) = [90, 180, 270].iter().find(|&&degrees| turned(from, degrees) == to) {
+        return vec![Op::Turn(degrees)];
+    }
+    shift((to.0 - from.0, to.1 - from.1))
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+fn divisors(n: i64) -> Vec<i64> {
+    let mut divisors = (1..).take_while(|d| d * d <= n)
+                            .filter(|d| n % d == 0)
+                            .flat_map(|d| vec![d, n / d])
+                            .collect::<Vec<i64>>();
+    divisors.sort_unstable();
+    divisors.dedup();
+    divisors
+}
+
+fn cross(a: Vector, b: Vector) -> i64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+// how many times forward with `u` and then `v` adds up to `target`, at least
+// once each
+fn split(target: Vector, u: Vector, v: Vector) -> Option<(i64, i64)> {
+    if u == (0, 0) || v == (0, 0) {
+        return None;
+    }
+    let det = cross(u, v);
+    if det != 0 {
+        let (k1, k2) = (cross(target, v), cross(u, target));
+        return match k1 % det == 0 && k2 % det == 0 && k1 / det > 0 && k2 / det > 0 {
+            true => Some((k1 / det, k2 / det)),
+            false => None,
+        };
+    }
+    // both along the same line, so only the lengths along it count
+    if cross(target, u) != 0 {
+        return None;
+    }
+    let unit = gcd(u.0.abs(), u.1.abs());
+    let direction = (u.0 / unit, u.1 / unit);
+    let along = |w: Vector| if direction.0 != 0 { w.0 / direction.0 } else { w.1 / direction.1 };
+    let (a, b, total) = (along(u), along(v), along(target));
+    if direction.0 * total != target.0 || direction.1 * total != target.1 {
+        return None;
+    }
+    // past this the same remainders come round again
+    (1..=total.abs() + 2 * (a.abs() + b.abs()) + 1).find_map(|k1| {
+                                                       let rest = total - k1 * a;
+                                                       match rest % b == 0 && rest / b > 0 {
+                                                           true => Some((k1, rest / b)),
+                                                           false => None,
+                                                       }
+                                                   })
+}
+
+// routes going forward twice with two instructions on the waypoint, one
+// before each time forward or one before the second and one after. Either
+// way the waypoint goes through one that's an instruction from both `from`
+// and `to`.
+fn bridges(from: Vector, to: Vector, target: Vector) -> Vec<Vec<Op>> {
+    let one = |a: Vector, b: Vector| retarget(a, b).len() == 1;
+    let mut vias = [90, 180, 270].iter().map(|&degrees| turned(from, degrees)).collect::<Vec<Vector>>();
+    vias.extend([90, 180, 270].iter().map(|&degrees| turned(to, 360 - degrees)));
+    vias.extend(vec![(to.0, from.1), (from.0, to.1)]);
+    // a line both lie on leaves one coordinate of the waypoint free, so
+    // pick it to make the distance come out
+    for swap in [false, true] {
+        let flip = |(x, y): Vector| if swap { (y, x) } else { (x, y) };
+        let (a, b, target) = (flip(from), flip(to), flip(target));
+        if a.1 != b.1 {
+            continue;
+        }
+        let line = a.1;
+        let repeats = match (line, target.1) {
+            (0, 0) => Some(2),
+            (0, _) => None,
+            (line, y) if y % line == 0 && y / line >= 2 => Some(y / line),
+            _ => None,
+        };
+        if let Some(repeats) = repeats {
+            // the free waypoint once, the fixed one for the rest
+            let fixed = repeats - 1;
+            vias.push(flip((target.0 - fixed * b.0, line)));
+            vias.push(flip((target.0 - fixed * a.0, line)));
+        }
+    }
+    vias.sort_unstable();
+    vias.dedup();
+
+    let mut routes = Vec::new();
+    for via in vias.into_iter().filter(|&via| one(from, via) && one(via, to)) {
+        if let Some((k1, k2)) = split(target, via, to) {
+            routes.push([retarget(from, via), vec![Op::Forward(k1)], retarget(via, to), vec![Op::Forward(k2)]].concat());
+        }
+        if let Some((k1, k2)) = split(target, from, via) {
+            routes.push([vec![Op::Forward(k1)], retarget(from, via), vec![Op::Forward(k2)], retarget(via, to)].concat());
+        }
+    }
+    routes
+}
+
+// turns the route into instructions and makes sure it gets there
+fn checked(ops: &[Op], mode: Mode, ship: (i32, i32), waypoint: Option<(i32, i32)>) -> Result<Vec<Instruction>, String> {
+    let count = |count: i64, op: &Op| i32::try_from(count).map_err(|_| match (op, mode) {
+        (Op::Shift(_), Mode::Waypoint) => format!("{} is too far to move the waypoint in one instruction", count),
+        (Op::Forward(_), Mode::Waypoint) => format!("{} is too many times to go to the waypoint in one instruction", count),
+        _ => format!("{} is too far to move the ship in one instruction", count),
+    });
+    let mut instructions = Vec::new();
+    for op in ops {
+        instructions.push(match *op {
+            Op::Shift((x, 0)) if x > 0 => Instruction { action: Action::East, count: count(x, op)? },
+            Op::Shift((x, 0)) => Instruction { action: Action::West, count: count(-x, op)? },
+            Op::Shift((_, y)) if y > 0 => Instruction { action: Action::North, count: count(y, op)? },
+            Op::Shift((_, y)) => Instruction { action: Action::South, count: count(-y, op)? },
+            Op::Turn(270) => Instruction { action: Action::Turn, count: -90 },
+            Op::Turn(degrees) => Instruction { action: Action::Turn, count: degrees },
+            Op::Forward(k) => Instruction { action: Action::Forward, count: count(k, op)? },
+        });
+    }
+
+    let ferry = navigate(&instructions, mode)?;
+    let ship = Point::new(ship.0, ship.1);
+    let waypoint = waypoint.map(|(x, y)| Point::new(x, y));
+    match ferry.ship == ship && waypoint.is_none_or(|waypoint| ferry.waypoint == waypoint) {
+        true => Ok(instructions),
+        false => Err(format!("the instructions ended up at {} rather than {}", ferry.ship, ship)),
+    }
+}
+
+fn to_instruction(input: &str, i: &str) -> Result<Instruction, ParseError> {
+    let split = i.char_indices().nth(1).map_or(i.len(), |(n, _)| n);
+    let (action, count) = i.split_at(split);
+    let digits = count;
+    let count = count.parse::<u32>().map_err(|_| ParseError::at(input, digits, Expected::Count))?;
+    // small enough that turning left can negate it
+    let count = i32::try_from(count).map_err(|_| ParseError::at(input, digits, Expected::CountInRange))?;
+    Ok(match action {
+        "N" => Instruction{ action: Action::North, count},
+        "E" => Instruction{ action: Action::East, count},
+        "S" => Instruction{ action: Action::South, count},
+        "W" => Instruction{ action: Action::West, count},
+        "L" => Instruction { action: Action::Turn, count: -count },
+        "R" => Instruction { action: Action::Turn, count },
+        "F" => Instruction { action: Action::Forward, count },
+        _ => return Err(ParseError::at(input, i, Expected::Action))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn part_2_test() {
+        let instructions = parse("F10\nN3\nF7\nR90\nF11").unwrap();
+        assert_eq!(part_2(&instructions), Ok(Fixed::from(286)));
+    }
+
+    #[test]
+    fn part_1_test() {
+        let instructions = parse("F10\nN3\nF7\nR90\nF11").unwrap();
+        assert_eq!(part_1(&instructions), Ok(Fixed::from(25)));
+    }
+
+    #[test]
+    fn parse_error_tests() {
+        let err = parse("F10\nN3\nX7").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (3, 1, Expected::Action));
+
+        let err = parse("F10\nN-3").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected.clone()), (2, 2, Expected::Count));
+        assert_eq!(err.to_string(), "line 2, column 2: expected a positive whole number, found '-3'");
+
+        let err = parse("L2147483648").err().unwrap();
+        assert_eq!(err.to_string(), "line 1, column 2: expected a count no bigger than 2147483647, found '2147483648'");
+        assert_eq!(parse("L2147483647").unwrap(), vec![Instruction { action: Action::Turn, count: -i32::MAX }]);
+
+        assert_eq!(parse("F10\nR45").unwrap()[1], Instruction { action: Action::Turn, count: 45 });
+    }
+
+    #[test]
+    fn navigation_tests() {
+        // turning through right angles stays exact however far round it goes
+        let ferry = navigate(&parse("R90\nF5\nL180\nF5\nR450\nL3600").unwrap(), Mode::Heading).unwrap();
+        assert_eq!((ferry.ship, ferry.heading), (Point::new(0, 0), 90));
+        assert_eq!(Point::new(10, 4).rotate(90), Some(Point::new(4, -10)));
+        assert_eq!(Point::new(10, 4).rotate(-270), Some(Point::new(4, -10)));
+
+        let ferry = navigate(&parse("R45\nF10").unwrap(), Mode::Heading).unwrap();
+        assert_eq!(ferry.ship, Point { x: Fixed::from_f64(7.07107), y: Fixed::from_f64(-7.07107) });
+        assert_eq!(ferry.ship.manhattan().unwrap().to_string(), "14.14214");
+        assert_eq!(Fixed::from_f64(-0.25).to_string(), "-0.25");
+
+        // other angles round, but only in the last place
+        let close = |a: Point, b: Point| (a.x.0 - b.x.0).abs() <= 4 && (a.y.0 - b.y.0).abs() <= 4;
+        let ferry = navigate(&parse(&["R45"; 8].join("\n")).unwrap(), Mode::Waypoint).unwrap();
+        assert!(close(ferry.waypoint, Point::new(10, 1)), "{}", ferry.waypoint);
+        let thirds = navigate(&parse("L30\nL30\nL30\nF2").unwrap(), Mode::Waypoint).unwrap();
+        let right = navigate(&parse("L90\nF2").unwrap(), Mode::Waypoint).unwrap();
+        assert!(close(thirds.ship, right.ship), "{} {}", thirds.ship, right.ship);
+        assert_eq!(right.ship, Point::new(-2, 20));
+
+        // a long file runs in a loop rather than a call per instruction
+        let long = vec![Instruction { action: Action::Forward, count: 1 }; 500_000];
+        assert_eq!(part_1(&long), Ok(Fixed::from(500_000)));
+        assert_eq!(part_2(&long), Ok(Fixed::from(5_500_000)));
+    }
+
+    #[test]
+    fn overflow_tests() {
+        let far = parse("N2000000000\nF2000000000").unwrap();
+        assert_eq!(part_1(&far).unwrap().to_string(), "4000000000");
+        assert_eq!(part_2(&far), Err("line 2: F2000000000 takes the ship too far to keep track of".to_owned()));
+        assert!(Ferry::new(Mode::Waypoint).track(&far).is_err());
+
+        // the ship can get further than its distance can be counted, and
+        // then further than it can be tracked
+        let corner = parse(&["F2147483647\nR90\nF2147483647\nL90"; 4400].join("\n")).unwrap();
+        assert_eq!(part_1(&corner[..4000]).unwrap().to_string(), "4294967294000");
+        let err = part_1(&corner[..8800]).unwrap_err();
+        assert_eq!(err, "the ship ends up at (4724464023400, -4724464023400), too far away to count");
+        let err = part_1(&corner).unwrap_err();
+        assert_eq!(err, "line 17177: F2147483647 takes the ship too far to keep track of");
+
+        // a failed instruction leaves the ferry where it was
+        let mut ferry = Ferry::new(Mode::Waypoint);
+        ferry.run(&parse("N2000000000\nF2000\nF2000").unwrap()).unwrap();
+        let before = ferry.clone();
+        assert!(ferry.execute(&Instruction { action: Action::Forward, count: 2000 }).is_err());
+        assert_eq!(ferry, before);
+
+        // turning by a huge count doesn't overflow the heading either
+        assert_eq!(navigate(&parse("R90\nR2147483647\nF1").unwrap(), Mode::Heading).unwrap().heading, 307);
+    }
+
+    #[test]
+    fn track_tests() {
+        let instructions = parse("F10\nN3\nF7\nR90\nF11").unwrap();
+        let track = Ferry::new(Mode::Heading).track(&instructions).unwrap();
+        let ships = track.fixes.iter().map(|fix| fix.ship).collect::<Vec<Point>>();
+        assert_eq!(ships, vec![Point::new(0, 0), Point::new(10, 0), Point::new(10, 3), Point::new(17, 3), Point::new(17, 3), Point::new(17, -8)]);
+        assert_eq!(track.fixes.iter().map(|fix| fix.heading).collect::<Vec<i32>>(), vec![90, 90, 90, 90, 180, 180]);
+        let svg = track.to_svg();
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert!(svg.contains("points=\"0,0 10,0 10,-3 17,-3 17,-3 17,8\""), "{}", svg);
+        assert!(!svg.contains("grey"));
+
+        let track = Ferry::new(Mode::Waypoint).track(&instructions).unwrap();
+        assert_eq!(track.fixes[5].ship, Point::new(214, -72));
+        assert_eq!(track.fixes[5].waypoint, Point::new(218, -82));
+        assert!(track.to_svg().contains("grey"));
+
+        let plot = Ferry::new(Mode::Heading).track(&parse("F4\nN2\nW4").unwrap()).unwrap().plot(5, 3);
+        assert_eq!(plot, "E####\n    #\nS####");
+    }
+
+    #[test]
+    fn synthesis_tests() {
+        let written = |instructions: Vec<Instruction>| instructions.iter().map(|instruction| instruction.to_string()).collect::<Vec<String>>().join("\n");
+        assert_eq!(written(synthesise_heading((17, -8)).unwrap()), "E17\nS8");
+        assert_eq!(written(synthesise_heading((0, 5)).unwrap()), "N5");
+        assert_eq!(written(synthesise_heading((0, 0)).unwrap()), "");
+
+        assert_eq!(written(synthesise_waypoint((0, 0), (10, 1)).unwrap()), "");
+        assert_eq!(written(synthesise_waypoint((0, 0), (1, -10)).unwrap()), "R90");
+        assert_eq!(written(synthesise_waypoint((100, 10), (10, 1)).unwrap()), "F10");
+        assert_eq!(written(synthesise_waypoint((100, 10), (10, -1)).unwrap()), "F10\nS2");
+        // forward, turn, forward beats lining the waypoint up once
+        assert_eq!(written(synthesise_waypoint((11, -9), (1, -10)).unwrap()), "F1\nR90\nF1");
+        let example = parse("F10\nN3\nF7\nR90\nF11").unwrap();
+        let ferry = navigate(&example, Mode::Waypoint).unwrap();
+        let route = synthesise_waypoint((214, -72), (4, -10)).unwrap();
+        assert!(route.len() <= example.len());
+        assert_eq!(navigate(&route, Mode::Waypoint), Ok(ferry));
+        assert_eq!(parse(&written(route.clone())).unwrap(), route);
+        assert_eq!(synthesise_waypoint((i32::MAX, 1), (0, 0)).unwrap().len(), 4);
+        assert_eq!(synthesise_heading((i32::MIN, 0)), Err("2147483648 is too far to move the ship in one instruction".to_owned()));
+        assert_eq!(synthesise_waypoint((0, 0), (i32::MIN, 5)), Err("2147483658 is too far to move the waypoint in one instruction".to_owned()));
+
+        // nothing with small counts gets anywhere in fewer instructions
+        let mut alphabet = Vec::new();
+        for count in 1..=4 {
+            for action in [Action::North, Action::East, Action::South, Action::West, Action::Forward] {
+                alphabet.push(Instruction { action, count });
+            }
+        }
+        for count in [90, 180, -90] {
+            alphabet.push(Instruction { action: Action::Turn, count });
+        }
+        let mut fewest = HashMap::new();
+        let mut frontier = vec![Ferry::new(Mode::Waypoint)];
+        for depth in 0..=4 {
+            let mut next = Vec::new();
+            for ferry in frontier {
+                let whole = |point: Point| ((point.x.0 / Fixed::SCALE) as i32, (point.y.0 / Fixed::SCALE) as i32);
+                fewest.entry((whole(ferry.ship), whole(ferry.waypoint))).or_insert(depth);
+                if depth < 4 {
+                    for instruction in &alphabet {
+                        let mut ferry = ferry.clone();
+                        ferry.execute(instruction).unwrap();
+                        next.push(ferry);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        for ((ship, waypoint), depth) in fewest {
+            let route = synthesise_waypoint(ship, waypoint).unwrap();
+            assert!(route.len() <= depth, "{:?} {:?} in {} rather than {}", ship, waypoint, written(route), depth);
+        }
+        // and the same for any target, checked by running the ferry
+        for x in -30..=30 {
+            for y in -30..=30 {
+                assert!(synthesise_waypoint((x, y), (y % 7, x % 5)).unwrap().len() <= 5);
+            }
+        }
+    }
+}
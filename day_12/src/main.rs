@@ -1,206 +1,66 @@
-use std::io::Read;
+use std::process;
 
-#[derive(Clone)]
-enum Action {
-    Turn,
-    Forward,
-    North,
-    East,
-    South,
-    West
-}
-
-type Direction = i32;
-const NORTH: Direction = 0;
-const EAST: Direction = 90;
-const SOUTH: Direction = 180;
-const WEST: Direction = 270;
-
-#[derive(Clone)]
-struct Instruction {
-    action: Action,
-    count: i32
-}
-
-struct Vessel {
-    heading: Direction,
-    x: i32,
-    y: i32
-}
-impl Vessel {
-
-}
+use common::{day_args, or_exit, read_or_exit};
+use day_12::{parse, part_1, part_2, synthesise_heading, synthesise_waypoint, Ferry, Instruction, Mode};
 
+const USAGE: &str = "usage: day_12 [input] [--waypoint] [--track | --svg | --plot <w>x<h> | --reach <x>,<y> [<waypoint x>,<y>]]";
 
 fn main() {
-    let contents = read_file("input.txt");
-    let instructions = contents.trim()
-                               .split("\n")
-                               .map(|instruction| {
-                                   let v = instruction.chars().collect::<Vec<char>>();
-                                   return to_instruction(v);
-                               })
-                               .collect::<Vec<Instruction>>();
-
-    part_1_test();
-    part_2_test();
-    let part_1_result = part_1(&instructions);
-    println!("part 1: {}", part_1_result);
-
-    let part_2_result = part_2(&instructions);
-    println!("part 2: {}", part_2_result);
-}
-
-fn part_2(instructions: &Vec<Instruction>) -> i32 {
-    let waypoint = Vessel {
-        heading: NORTH, // Doesn't matter in this scenario
-        x: 10,
-        y: 1
-    };
-
-    let vessel = tick_2(Vessel{ heading: NORTH, x: 0, y: 0}, waypoint, instructions[..].to_vec());
-
-    let mut x = vessel.x;
-    let mut y = vessel.y;
-    if x < 0 {
-        x *= -1;
+    let (input, options) = day_args();
+    let instructions = or_exit(parse(&read_or_exit(&input)));
+
+    let mut options = options.iter().map(|option| option.as_str()).collect::<Vec<&str>>();
+    let mut mode = Mode::Heading;
+    if let Some(i) = options.iter().position(|option| *option == "--waypoint") {
+        options.remove(i);
+        mode = Mode::Waypoint;
     }
-    if y < 0 {
-        y *= -1;
+    let track = || or_exit(Ferry::new(mode).track(&instructions));
+
+    match options[..] {
+        [] => {
+            let part_1_result = or_exit(part_1(&instructions));
+            println!("part 1: {}", part_1_result);
+
+            let part_2_result = or_exit(part_2(&instructions));
+            println!("part 2: {}", part_2_result);
+        },
+        ["--track"] => {
+            for fix in track().fixes {
+                match mode {
+                    Mode::Heading => println!("ship {} heading {}", fix.ship, fix.heading),
+                    Mode::Waypoint => println!("ship {} waypoint {}", fix.ship, fix.waypoint),
+                }
+            }
+        },
+        ["--svg"] => print!("{}", track().to_svg()),
+        ["--plot", size] => match size.split_once('x').map(|(width, height)| (width.parse(), height.parse())) {
+            Some((Ok(width), Ok(height))) => println!("{}", track().plot(width, height)),
+            _ => usage(),
+        },
+        ["--reach", ship] if mode == Mode::Heading => print_route(synthesise_heading(point(ship))),
+        ["--reach", ship, waypoint] if mode == Mode::Waypoint => print_route(synthesise_waypoint(point(ship), point(waypoint))),
+        _ => usage(),
     }
-    x + y
 }
 
-fn tick_2(mut vessel: Vessel, mut waypoint: Vessel, instructions: Vec<Instruction>) -> Vessel {
-    match instructions[0].action {
-        Action::Turn => waypoint = rotate(waypoint, instructions[0].count),
-        Action::Forward => vessel = move_to_point(vessel, &waypoint, instructions[0].count),
-        Action::North => waypoint = sail(waypoint, NORTH, instructions[0].count),
-        Action::East => waypoint = sail(waypoint, EAST, instructions[0].count),
-        Action::South => waypoint = sail(waypoint, SOUTH, instructions[0].count),
-        Action::West => waypoint = sail(waypoint, WEST, instructions[0].count),
-    };
-
-    if instructions.len() == 1 {
-        return vessel;
+// one instruction per line, so the route can be read back in as an input
+fn print_route(route: Result<Vec<Instruction>, String>) {
+    let route = or_exit(route);
+    for instruction in &route {
+        println!("{}", instruction);
     }
-    tick_2(vessel, waypoint, instructions[1..].to_vec())
-}
-
-fn move_to_point(mut vessel: Vessel, waypoint: &Vessel, count: i32) -> Vessel {
-    vessel.x += waypoint.x * count;
-    vessel.y += waypoint.y * count;
-    vessel
+    eprintln!("{} instructions, checked by running them", route.len());
 }
 
-fn rotate(mut waypoint: Vessel, mut degrees: i32) -> Vessel {
-    if degrees > 0 {
-        degrees -= 90;
-        let new_y = waypoint.x * -1;
-        waypoint.x = waypoint.y;
-        waypoint.y = new_y;
-    } else {
-        degrees += 90;
-        let new_x = waypoint.y * -1;
-        waypoint.y = waypoint.x;
-        waypoint.x = new_x;
-    }
-    if degrees == 0 {
-        return waypoint;
+fn point(point: &str) -> (i32, i32) {
+    match point.split_once(',').map(|(x, y)| (x.parse(), y.parse())) {
+        Some((Ok(x), Ok(y))) => (x, y),
+        _ => usage(),
     }
-    rotate(waypoint, degrees)
 }
 
-fn part_1(instructions: &Vec<Instruction>) -> i32 {
-    let vessel = tick_1(Vessel{ heading: EAST, x: 0, y: 0}, instructions[..].to_vec());
-
-    let mut x = vessel.x;
-    let mut y = vessel.y;
-    if x < 0 {
-        x *= -1;
-    }
-    if y < 0 {
-        y *= -1;
-    }
-    x + y
-}
-
-fn tick_1(mut vessel: Vessel, instructions: Vec<Instruction>) -> Vessel {
-    vessel = match instructions[0].action {
-        Action::Turn => turn(vessel, instructions[0].count),
-        Action::Forward => {
-            let heading = vessel.heading;
-            sail(vessel, heading, instructions[0].count)
-        }
-        Action::North => sail(vessel, NORTH, instructions[0].count),
-        Action::East => sail(vessel, EAST, instructions[0].count),
-        Action::South => sail(vessel, SOUTH, instructions[0].count),
-        Action::West => sail(vessel, WEST, instructions[0].count),
-    };
-
-    if instructions.len() == 1 {
-        return vessel;
-    }
-    tick_1(vessel, instructions[1..].to_vec())
-}
-
-fn sail(mut vessel: Vessel, heading: Direction, count: i32) -> Vessel {
-    match heading {
-        NORTH => vessel.y += count,
-        SOUTH => vessel.y -= count,
-        EAST => vessel.x += count,
-        WEST => vessel.x -= count,
-        _ => panic!("where are we going?")
-    }
-    vessel
-}
-
-fn turn(mut vessel: Vessel, change: i32) -> Vessel {
-    vessel.heading = (vessel.heading + change + 360) % 360;
-    vessel
-}
-
-
-fn to_instruction(i: Vec<char>) -> Instruction {
-    let count = i[1..].into_iter().collect::<String>().parse::<i32>().unwrap();
-    match i[0] {
-        'N' => Instruction{ action: Action::North, count},
-        'E' => Instruction{ action: Action::East, count},
-        'S' => Instruction{ action: Action::South, count},
-        'W' => Instruction{ action: Action::West, count},
-        'L' => Instruction { action: Action::Turn, count: count * -1 },
-        'R' => Instruction { action: Action::Turn, count },
-        'F' => Instruction { action: Action::Forward, count },
-        _ => panic!("Uh oh?")
-    }
-}
-
-fn part_2_test() {
-    let instructions = vec![
-        to_instruction("F10".chars().collect::<Vec<char>>()),
-        to_instruction("N3".chars().collect::<Vec<char>>()),
-        to_instruction("F7".chars().collect::<Vec<char>>()),
-        to_instruction("R90".chars().collect::<Vec<char>>()),
-        to_instruction("F11".chars().collect::<Vec<char>>()),
-    ];
-    assert_eq!(part_2(&instructions), 286);
-}
-
-fn part_1_test() {
-    let instructions = vec![
-        to_instruction("F10".chars().collect::<Vec<char>>()),
-        to_instruction("N3".chars().collect::<Vec<char>>()),
-        to_instruction("F7".chars().collect::<Vec<char>>()),
-        to_instruction("R90".chars().collect::<Vec<char>>()),
-        to_instruction("F11".chars().collect::<Vec<char>>()),
-    ];
-    assert_eq!(part_1(&instructions), 25);
-}
-
-fn read_file(filename: &str) -> String {
-    let mut file = std::fs::File::open(filename).expect("Failed to open file");
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).expect("Failed to read file");
-
-    contents
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
 }
@@ -0,0 +1,156 @@
+use std::fmt;
+
+use common::Answers;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Timestamp,
+    Bus,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Timestamp => write!(f, "an arrival time"),
+            Expected::Bus => write!(f, "a bus id or 'x'"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = (i64, Vec<Option<i64>>);
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    let mut parts = input.split("\n");
+    let arrival = parts.next().unwrap_or(input);
+    let schedule = parts.next().unwrap_or(&input[input.len()..]);
+
+    let arrival = arrival.parse::<i64>().map_err(|_| ParseError::at(input, arrival, Expected::Timestamp))?;
+    let buses = schedule.trim()
+                        .split(",")
+                        .map(|bus| match bus {
+                            "x" => Ok(None),
+                            _ => match bus.parse::<i64>() {
+                                Ok(id) if id > 0 => Ok(Some(id)),
+                                _ => Err(ParseError::at(input, bus, Expected::Bus)),
+                            }
+                        })
+                        .collect::<Result<Vec<Option<i64>>, ParseError>>()?;
+    Ok((arrival, buses))
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let notes = parse(input)?;
+    Ok(Answers::new(part_1(&notes), part_2(&notes)))
+}
+
+pub fn part_1((arrival, schedule): &Parsed) -> i64 {
+    let buses = schedule.iter().flatten().copied().collect::<Vec<i64>>();
+    earliest_bus(*arrival, buses)
+}
+
+pub fn part_2((_, schedule): &Parsed) -> i64 {
+    earliest_timestamp(schedule)
+}
+
+pub fn earliest_bus(time: i64, mut buses: Vec<i64>) -> i64 {
+    buses.sort();
+    let mut smallest: (i64, i64) = (buses[buses.len() - 1], time * time); // some arbitrarily large number to avoid using smallest unassigned
+    for bus in buses {
+        let mut bus_time = bus;
+        while bus_time < time {
+            bus_time += bus;
+        }
+        let diff = bus_time - time;
+        if smallest.1 > diff {
+            smallest.0 = bus;
+            smallest.1 = diff;
+        }
+    }
+        smallest.0 * smallest.1
+}
+
+pub fn earliest_timestamp(buses: &[Option<i64>]) -> i64 {
+    let mut time: i64 = 0;
+
+    let mut inc = buses[0].unwrap();
+
+    for (offset, bus) in (1_i64..).zip(buses[1..].iter()) {
+        if let Some(bus) = bus {
+            while (time + offset) % bus != 0 {
+                time += inc;
+            }
+            inc *= bus;
+        }
+    }
+
+    time
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_test() {
+        let parts = ["939",
+            "7,13,x,x,59,x,31,19"];
+        let arrival = parts[0].parse::<i64>().unwrap();
+        let buses = parts[1].trim()
+                                .replace("x,", "")
+                                .split(",")
+                                .map(|bus| bus.parse::<i64>().unwrap())
+                                .collect::<Vec<i64>>();
+        assert_eq!(earliest_bus(arrival, buses), 295);
+    }
+
+    #[test]
+    fn part_2_test() {
+        let sets = vec! [
+            vec![
+                "939",
+                "67,7,59,61"
+            ],
+            vec![
+                "939",
+                "67,x,7,59,61"
+            ],
+            vec![
+                "939",
+                "67,7,x,59,61"
+            ],
+            vec![
+                "939",
+                "1789,37,47,1889"
+            ],
+
+            vec![
+                "939",
+                "17,x,13,19"
+            ],
+            vec![
+                "939",
+                "7,13,x,x,59,x,31,19"
+            ],
+        ];
+
+        let mut inputs: Vec<Vec<Option<i64>>> = Vec::new();
+
+        for set in sets {
+            inputs.push(set[1].trim()
+                        .split(",")
+                        .map(|bus| {
+                            bus.parse::<i64>().ok()
+                        })
+                        .collect::<Vec<Option<i64>>>());
+
+        }
+        assert_eq!(earliest_timestamp(&inputs[0]), 754018);
+        assert_eq!(earliest_timestamp(&inputs[1]), 779210);
+        assert_eq!(earliest_timestamp(&inputs[2]), 1261476);
+        assert_eq!(earliest_timestamp(&inputs[3]), 1202161486);
+        assert_eq!(earliest_timestamp(&inputs[4]), 3417);
+        assert_eq!(earliest_timestamp(&inputs[5]), 1068781);
+    }
+}
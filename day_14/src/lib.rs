@@ -0,0 +1,201 @@
+use common::Answers;
+use std::collections::HashMap;
+use std::fmt;
+
+pub enum Instruction {
+    Mask(Vec<char>),
+    Mem(u64, u64)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Instruction,
+    Mask,
+    Address,
+    Assignment,
+    Value,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Instruction => write!(f, "'mask = ' or 'mem['"),
+            Expected::Mask => write!(f, "36 of '0', '1' or 'X'"),
+            Expected::Address => write!(f, "a memory address"),
+            Expected::Assignment => write!(f, "'] = '"),
+            Expected::Value => write!(f, "a 36 bit value"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Vec<Instruction>;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    input.trim()
+            .split('\n')
+            .map(|instruction| {
+                if let Some(mask) = instruction.strip_prefix("mask = ") {
+                    if mask.len() != 36 || mask.chars().any(|c| c != '0' && c != '1' && c != 'X') {
+                        return Err(ParseError::at(input, mask, Expected::Mask));
+                    }
+                    return Ok(Instruction::Mask(mask.chars().collect::<Vec<char>>()));
+                }
+                let assignment = instruction.strip_prefix("mem[")
+                                            .ok_or_else(|| ParseError::at(input, instruction, Expected::Instruction))?;
+                let (address, value) = assignment.split_once("] = ")
+                                                 .ok_or_else(|| ParseError::at(input, assignment, Expected::Assignment))?;
+                let address = address.parse::<u64>().map_err(|_| ParseError::at(input, address, Expected::Address))?;
+                let value = match value.parse::<u64>() {
+                    Ok(val) if val < 1 << 36 => val,
+                    _ => return Err(ParseError::at(input, value, Expected::Value)),
+                };
+                Ok(Instruction::Mem(address, value))
+            })
+            .collect::<Result<Vec<Instruction>, ParseError>>()
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let instructions = parse(input)?;
+    Ok(Answers::new(part_1(&instructions), part_2(&instructions)))
+}
+
+pub fn part_1(instructions: &[Instruction]) -> u64 {
+    let mut heap = HashMap::new();
+
+    let mut mask: &Vec<char> = &Vec::new();
+    for instruction in instructions {
+        match instruction {
+            Instruction::Mask(m) => mask = m,
+            Instruction::Mem(i, val) => {heap.insert(*i, apply_mask_1(*val, mask));}
+        }
+    }
+
+    let mut total: u64 = 0;
+    for (_key, val) in heap.iter() {
+        total += val;
+    }
+    total
+}
+
+pub fn part_2(instructions: &[Instruction]) -> u64 {
+    let mut heap = HashMap::new();
+
+    let mut mask: Vec<char> = Vec::new();
+    for instruction in instructions {
+        match instruction {
+            Instruction::Mask(m) => mask = m.clone(),
+            Instruction::Mem(i, val) => {
+                let address = apply_mask_2(u64_to_vec(*i), &mask);
+                let addresses = address_variants(address, &mask);
+                for address in addresses {
+                    heap.insert(address, val);
+                }
+            }
+        }
+    }
+
+    let mut total: u64 = 0;
+    for (_key, val) in heap.iter() {
+        total += **val;
+    }
+    total
+}
+
+fn address_variants(address: Vec<char>, mask: &[char]) -> Vec<u64> {
+    if !mask.contains(&'X') {
+        return vec![vec_to_u64(address)];
+    }
+    let mut variants: Vec<u64> = Vec::new();
+    for (i, c) in mask.iter().enumerate() {
+        match c {
+            'X' => {
+                let mut v_mask = mask.to_vec();
+                let mut v_address_0 = address.clone();
+                let mut v_address_1 = address.clone();
+                v_mask[i] = '0';
+                v_address_1[i] = '1';
+                v_address_0[i] = '0';
+                variants.append(&mut address_variants(v_address_1, &v_mask.clone()));
+                variants.append(&mut address_variants(v_address_0, &v_mask.clone()));
+                break;
+            },
+            _ => continue
+        }
+    }
+    variants
+}
+
+fn apply_mask_2(mut address: Vec<char>, mask: &[char]) -> Vec<char> {
+    for (i, c) in mask.iter().enumerate() {
+        match c {
+            '1' => address[i] = '1',
+            _ => continue
+        }
+    }
+    address
+}
+
+fn apply_mask_1(val: u64, mask: &[char]) -> u64 {
+    let mut val = u64_to_vec(val);
+
+    for (i, c) in mask.iter().enumerate() {
+        match c {
+            'X' => continue,
+            _ => val[i] = *c
+        }
+    }
+    vec_to_u64(val)
+}
+
+fn vec_to_u64(address: Vec<char>) -> u64 {
+    isize::from_str_radix(address.iter().collect::<String>().as_str(), 2).unwrap() as u64
+}
+
+fn u64_to_vec(address: u64) -> Vec<char> {
+    let mut address = format!("{:b}", address).chars().collect::<Vec<char>>();
+    while address.len() < 36 {
+        address.insert(0, '0');
+    }
+    address
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mask_test(mask: &[char]) {
+        assert_eq!(apply_mask_1(11, mask), 73);
+        assert_eq!(apply_mask_1(101, mask), 101);
+        assert_eq!(apply_mask_1( 0, mask), 64);
+    }
+
+    #[test]
+    fn part_1_test() {
+        let mask = Instruction::Mask("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X".chars().collect::<Vec<char>>());
+        let instructions = vec![
+            mask,
+            Instruction::Mem(8, 11),
+            Instruction::Mem(7, 101),
+            Instruction::Mem(8, 0),
+        ];
+        if let Instruction::Mask(mask) = &instructions[0] {
+            mask_test(mask);
+        }
+
+        assert_eq!(part_1(&instructions), 165);
+    }
+
+    #[test]
+    fn part_2_test() {
+        let instructions = vec![
+            Instruction::Mask("000000000000000000000000000000X1001X".chars().collect::<Vec<char>>()),
+            Instruction::Mem(42, 100),
+            Instruction::Mask("00000000000000000000000000000000X0XX".chars().collect::<Vec<char>>()),
+            Instruction::Mem(26, 1),
+        ];
+
+        assert_eq!(part_2(&instructions), 208);
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use common::Answers;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Number,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Number => write!(f, "a starting number"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Vec<usize>;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    input.trim()
+         .split(",")
+         .map(|n| n.parse::<usize>().map_err(|_| ParseError::at(input, n, Expected::Number)))
+         .collect::<Result<Vec<usize>, ParseError>>()
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let starters = parse(input)?;
+    Ok(Answers::new(part_1(&starters), part_2(&starters)))
+}
+
+pub fn part_1(starters: &[usize]) -> usize {
+    play(starters, 2020)
+}
+
+pub fn part_2(starters: &[usize]) -> usize {
+    play(starters, 30000000)
+}
+
+pub fn play(input: &[usize], num: usize) -> usize {
+    let mut history = input.to_vec();
+    let mut memo = HashMap::new();
+    for (i, starter) in history[0..history.len() - 1].iter().enumerate() {
+        memo.insert(*starter, i);
+    }
+    let mut turn = history.len() - 1;
+    let mut last = history[turn];
+    loop {
+        if turn == num - 1 {
+            break;
+        }
+        let next: usize = match memo.get(&last) {
+            Some(i) => turn - *i,
+            None => 0
+        };
+        memo.insert(last, turn);
+        history.push(next);
+        last = next;
+        turn += 1;
+    }
+    history[turn]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_test() {
+        let inputs = [
+            ("0,3,6", 436),
+            ("1,3,2", 1),
+            ("2,1,3", 10),
+            ("1,2,3", 27),
+            ("2,3,1", 78),
+            ("3,2,1", 438),
+            ("3,1,2", 1836)
+        ];
+        for input in inputs.iter() {
+            let nums = parse(input.0).unwrap();
+            assert_eq!(part_1(&nums), input.1);
+        }
+    }
+}
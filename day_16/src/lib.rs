@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use common::Answers;
+
+#[derive(Clone)]
+pub struct Rule {
+    name: String,
+    ranges: Vec<(u64, u64)>
+}
+
+#[derive(Clone)]
+struct Value {
+    rules: Vec<String>
+}
+
+type Ticket = Vec<Value>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    RuleName,
+    Range,
+    Or,
+    Number,
+    YourTicket,
+    NearbyTickets,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::RuleName => write!(f, "'<field>: '"),
+            Expected::Range => write!(f, "'<start>-<end>'"),
+            Expected::Or => write!(f, "two ranges joined by ' or '"),
+            Expected::Number => write!(f, "a number"),
+            Expected::YourTicket => write!(f, "'your ticket:'"),
+            Expected::NearbyTickets => write!(f, "'nearby tickets:'"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = (Vec<Rule>, Vec<u64>, Vec<Vec<u64>>);
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    let parts = input.split("\n\n").collect::<Vec<&str>>();
+    build_parts(input, parts)
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let notes = parse(input)?;
+    Ok(Answers::new(part_1(&notes), part_2(&notes)))
+}
+
+pub fn part_1((rules, _, nearby_tickets): &Parsed) -> u64 {
+    error_rate(nearby_tickets, rules)
+}
+
+pub fn part_2((rules, my_ticket, nearby_tickets): &Parsed) -> u64 {
+    departure_product(my_ticket, nearby_tickets, rules)
+}
+
+fn departure_product(my_ticket: &[u64], nearby_tickets: &[Vec<u64>], rules: &[Rule]) -> u64 {
+    let valid = valid_tickets(nearby_tickets, rules);
+
+    let mut rule_indices: HashMap<usize, String> = HashMap::new();
+    let mut missing_rule_indices = (0..my_ticket.len()).collect::<Vec<usize>>();
+    while rule_indices.iter().len() < rules.len() {
+        let mut missing: Vec<usize> = Vec::new();
+        for i in missing_rule_indices {
+            let used_rules = rule_indices.values().cloned().collect::<Vec<String>>();
+            let mut vals: Vec<Value> = Vec::new();
+            for ticket in &valid {
+                vals.push(ticket[i].clone());
+            }
+            match rules_from_values(vals, rules, used_rules) {
+                Ok(rules) => {
+                    rule_indices.insert(i, rules[0].clone());
+                },
+                Err(_rules) => missing.push(i)
+            }
+        }
+        missing_rule_indices = missing;
+    }
+    let mut total: u64 = 1;
+    for (i, val) in my_ticket.iter().enumerate() {
+        match rule_indices.get(&i) {
+            Some(rule) => {
+                if rule.as_str().contains("departure") {
+                    total *= val;
+                }
+            },
+            None => panic!("no rule?")
+        }
+    }
+    total
+}
+
+fn rules_from_values(values: Vec<Value>, rules: &[Rule], used_rules: Vec<String>) -> Result<Vec<String>, Vec<String>> {
+    let rules = rules.iter().filter(|rule| !used_rules.contains(&rule.name)).collect::<Vec<&Rule>>();
+    let mut valid_rules: Vec<String> = Vec::new();
+    for rule in rules {
+        let mut valid = true;
+        for value in &values {
+            if !value.rules.contains(&rule.name) {
+                valid = false;
+                break;
+            }
+        }
+        if valid {
+            valid_rules.push(rule.name.clone());
+        }
+    }
+    if valid_rules.len() == 1 {
+        return Ok(valid_rules);
+    }
+    Err(valid_rules)
+}
+
+fn valid_tickets(tickets: &[Vec<u64>], rules: &[Rule]) -> Vec<Ticket> {
+    let mut valid: Vec<Ticket> = Vec::new();
+    for ticket in tickets {
+        match valid_ticket(ticket, rules) {
+            Ok(validated) => valid.push(validated),
+            Err(_) => continue
+        }
+    }
+    valid
+}
+
+fn valid_ticket(ticket: &[u64], rules: &[Rule]) -> Result<Ticket, ()> {
+    let mut validated: Ticket = Vec::new();
+    for val in ticket {
+        match valid_value(val, rules) {
+            Ok(rules) => validated.push(Value { rules }),
+            Err(_) => return Err(())
+        }
+    }
+    Ok(validated)
+}
+
+fn error_rate(tickets: &[Vec<u64>], rules: &[Rule]) -> u64 {
+    let mut values: Vec<u64> = Vec::new();
+    let total = sum_tickets(tickets);
+    for ticket in tickets {
+        values.append(&mut valid_values(ticket, rules));
+    }
+    total - values.iter().sum::<u64>()
+}
+
+fn valid_values(ticket: &[u64], rules: &[Rule]) -> Vec<u64> {
+    let mut values: Vec<u64> = Vec::new();
+    for val in ticket {
+        match valid_value(val, rules) {
+            Ok(_) => values.push(*val),
+            Err(_) => continue
+        }
+    }
+    values
+}
+
+fn valid_value(val: &u64, rules: &[Rule]) -> Result<Vec<String>, ()> {
+    let mut valid_for: Vec<String> = Vec::new();
+    for rule in rules {
+        if (rule.ranges[0].0..=rule.ranges[0].1).contains(val) ||
+            (rule.ranges[1].0..=rule.ranges[1].1).contains(val) {
+                valid_for.push(rule.name.clone());
+            }
+    }
+    match !valid_for.is_empty() {
+        true => Ok(valid_for),
+        false => Err(())
+    }
+}
+
+fn sum_tickets(tickets: &[Vec<u64>]) -> u64 {
+    let mut total = 0;
+    for ticket in tickets {
+        for val in ticket {
+            total += val;
+        }
+    }
+    total
+}
+
+fn build_parts(input: &str, parts: Vec<&str>) -> Result<Parsed, ParseError> {
+    let end = &input[input.len()..];
+    let rules = build_rules(input, parts[0])?;
+    let my_ticket = parts.get(1).copied().unwrap_or(end).trim();
+    let my_ticket = my_ticket.strip_prefix("your ticket:\n")
+                             .ok_or_else(|| ParseError::at(input, my_ticket, Expected::YourTicket))?;
+    let my_ticket = build_ticket(input, my_ticket)?;
+    let nearby_tickets = parts.get(2).copied().unwrap_or(end).trim();
+    let nearby_tickets = nearby_tickets.strip_prefix("nearby tickets:\n")
+                                       .ok_or_else(|| ParseError::at(input, nearby_tickets, Expected::NearbyTickets))?
+                                       .split("\n")
+                                       .map(|ticket| build_ticket(input, ticket))
+                                       .collect::<Result<Vec<Vec<u64>>, ParseError>>()?;
+    Ok((rules, my_ticket, nearby_tickets))
+}
+
+fn build_rules(input: &str, rules: &str) -> Result<Vec<Rule>, ParseError> {
+    rules.split('\n').map(|rule| {
+        let (name, ranges) = rule.split_once(": ")
+                                 .ok_or_else(|| ParseError::at(input, rule, Expected::RuleName))?;
+        Ok(Rule {
+            name: name.to_owned(),
+            ranges: build_range_set(input, ranges)?
+        })
+    }).collect::<Result<Vec<Rule>, ParseError>>()
+}
+
+// tickets are only ever checked against a rule's first two ranges
+fn build_range_set(input: &str, ranges: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+    let ranges = ranges.split(" or ").collect::<Vec<&str>>();
+    if ranges.len() != 2 {
+        let last = ranges[ranges.len() - 1];
+        return Err(ParseError::at(input, &last[last.len()..], Expected::Or));
+    }
+    ranges.iter()
+          .map(|range| {
+              let (start, end) = range.split_once('-')
+                                      .ok_or_else(|| ParseError::at(input, range, Expected::Range))?;
+              let start = start.parse::<u64>().map_err(|_| ParseError::at(input, start, Expected::Number))?;
+              let end = end.parse::<u64>().map_err(|_| ParseError::at(input, end, Expected::Number))?;
+              Ok((start, end))
+          })
+          .collect::<Result<Vec<(u64, u64)>, ParseError>>()
+}
+
+fn build_ticket(input: &str, ticket: &str) -> Result<Vec<u64>, ParseError> {
+    ticket.split(",")
+          .map(|num| num.parse::<u64>().map_err(|_| ParseError::at(input, num, Expected::Number)))
+          .collect::<Result<Vec<u64>, ParseError>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_test() {
+        let input = "class: 1-3 or 5-7\n\
+                     row: 6-11 or 33-44\n\
+                     seat: 13-40 or 45-50\n\
+                     \n\
+                     your ticket:\n\
+                     7,1,14\n\
+                     \n\
+                     nearby tickets:\n\
+                     7,3,47\n\
+                     40,4,50\n\
+                     55,2,20\n\
+                     38,6,12";
+        assert_eq!(part_1(&parse(input).unwrap()), 71);
+    }
+
+    #[test]
+    fn parse_error_tests() {
+        let err = parse("class: 1-3 or 5-7\n\
+                         row: 6-11 or 33-x\n\
+                         \n\
+                         your ticket:\n\
+                         7,1\n\
+                         \n\
+                         nearby tickets:\n\
+                         7,3").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (2, 17, Expected::Number));
+
+        let err = parse("class 1-3 or 5-7").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (1, 1, Expected::RuleName));
+
+        let err = parse("class: 1-3\n\nyour ticket:\n7").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (1, 11, Expected::Or));
+
+        let err = parse("class: 1-3 or 5-7\n\nyour ticket:\n7").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (4, 2, Expected::NearbyTickets));
+    }
+}
@@ -0,0 +1,79 @@
+use common::automaton::{Automaton, Lattice, Neighbourhood, Rule};
+use common::{Answers, Grid};
+use std::fmt;
+
+pub type Layer = Grid<char>;
+
+const CYCLES: usize = 6;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Cube,
+    EndOfRow,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Cube => write!(f, "'.' or '#'"),
+            Expected::EndOfRow => write!(f, "the end of the row"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Layer;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    let cube = |c| match c {
+        '.' | '#' => Some(c),
+        _ => None,
+    };
+    Grid::parse(input, cube, Expected::Cube, Expected::EndOfRow)
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let layer = parse(input)?;
+    Ok(Answers::new(part_1(&layer), part_2(&layer)))
+}
+
+pub fn part_1(z0: &Layer) -> u32 {
+    let active = active_cubes(z0).map(|(x, y)| [x, y, 0]);
+    boot(Lattice::<3>::new(Neighbourhood::Moore), active)
+}
+
+pub fn part_2(z0: &Layer) -> u32 {
+    let active = active_cubes(z0).map(|(x, y)| [x, y, 0, 0]);
+    boot(Lattice::<4>::new(Neighbourhood::Moore), active)
+}
+
+fn active_cubes(z0: &Layer) -> impl Iterator<Item = (i32, i32)> + '_ {
+    z0.cells()
+      .filter(|(_, cell)| **cell == '#')
+      .map(|((x, y), _)| (x as i32, y as i32))
+}
+
+// active cubes stay active with 2 or 3 active neighbours, inactive ones need exactly 3
+fn boot<const N: usize>(space: Lattice<N>, active: impl Iterator<Item = [i32; N]>) -> u32 {
+    let mut pocket = Automaton::new(space, Rule::new(&[3], &[2, 3]), active);
+    pocket.run(CYCLES);
+    pocket.population() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_2_test() {
+        let start = parse(".#.\n..#\n###").unwrap();
+        assert_eq!(part_2(&start), 848);
+    }
+
+    #[test]
+    fn part_1_test() {
+        let start = parse(".#.\n..#\n###").unwrap();
+        assert_eq!(part_1(&start), 112);
+    }
+}
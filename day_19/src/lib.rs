@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+use std::fmt;
+use regex::Regex;
+
+use common::Answers;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    RuleId,
+    SubRule,
+    KnownRule,
+    Messages,
+    Message,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::RuleId => write!(f, "'<number>: '"),
+            Expected::SubRule => write!(f, "a rule number, '|' or a quoted letter"),
+            Expected::KnownRule => write!(f, "the number of a rule in the list"),
+            Expected::Messages => write!(f, "a blank line followed by messages"),
+            Expected::Message => write!(f, "'a' or 'b'"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = (Vec<String>, Vec<String>);
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    let (rules, messages) = input.split_once("\n\n")
+                                 .ok_or_else(|| ParseError::at(input, &input[input.len()..], Expected::Messages))?;
+    check_rules(input, rules)?;
+    let rules = rules.split('\n')
+                     .map(|r| {
+                         r.replace("\"", "")
+                     })
+                     .collect::<Vec<String>>();
+    let messages = messages.split('\n')
+                           .map(|message| match message.char_indices().find(|(_, c)| *c != 'a' && *c != 'b') {
+                               Some((i, _)) => Err(ParseError::at(input, &message[i..], Expected::Message)),
+                               None => Ok(message.to_owned()),
+                           })
+                           .collect::<Result<Vec<String>, ParseError>>()?;
+    Ok((rules, messages))
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let notes = parse(input)?;
+    Ok(Answers::new(part_1(&notes), part_2(&notes)))
+}
+
+// parse_rules keeps retrying rules until it can build them, so a reference to a
+// missing rule has to be caught here or it never finishes
+fn check_rules(input: &str, rules: &str) -> Result<(), ParseError> {
+    let mut ids: Vec<&str> = Vec::new();
+    let mut bodies: Vec<&str> = Vec::new();
+    for rule in rules.split('\n') {
+        match rule.split_once(": ") {
+            Some((id, body)) if id.parse::<u32>().is_ok() => {
+                ids.push(id);
+                bodies.push(body);
+            },
+            _ => return Err(ParseError::at(input, rule, Expected::RuleId)),
+        }
+    }
+    for body in bodies {
+        if body == "\"a\"" || body == "\"b\"" {
+            continue;
+        }
+        for sub in body.split(' ') {
+            if sub == "|" {
+                continue;
+            }
+            if sub.parse::<u32>().is_err() {
+                return Err(ParseError::at(input, sub, Expected::SubRule));
+            }
+            if !ids.contains(&sub) {
+                return Err(ParseError::at(input, sub, Expected::KnownRule));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn part_2((rules, messages): &Parsed) -> u32 {
+    // {N} instead of {1,} because they have to be the same length!
+    let rules = replace_rule("11: 42 31", "11: 42 31 | 42 {N} 31 {N}", rules);
+    let rules = replace_rule("8: 42", "8: 42 | 42 {1,}", &rules);
+    let rules = parse_rules(rules);
+
+    let mut regex = rules.get("0").unwrap().clone();
+    regex.push('$');
+    regex.insert(0, '^');
+
+
+    let mut matched: Vec<&str> = Vec::new();
+    for i in 1..10 {
+        let regex = regex.replace("N", i.to_string().as_str());
+        let re = Regex::new(regex.as_str()).unwrap();
+        for message in messages {
+            if re.is_match(message) {
+                matched.push(message.as_str());
+            }
+        }
+    }
+
+    let mut dedup: Vec<&str> = Vec::new();
+    for m in matched {
+        if dedup.contains(&m) {
+            continue
+        }
+        dedup.push(m);
+    }
+    dedup.len() as u32
+}
+
+fn replace_rule(old: &str, new: &str, rules: &[String]) -> Vec<String> {
+    let mut rules = rules.to_vec();
+    if let Some(i) = rules.iter().position(|r| r == old) {
+        rules[i] = new.to_owned();
+    }
+    rules
+}
+
+pub fn part_1((rules, messages): &Parsed) -> u32 {
+    let rules = parse_rules(rules.to_vec());
+
+    let mut regex = rules.get("0").unwrap().clone();
+    regex.push('$');
+    regex.insert(0, '^');
+    let re = Regex::new(regex.as_str()).unwrap();
+
+    let mut count = 0;
+    for message in messages {
+        if re.is_match(message) {
+            count += 1
+        }
+    }
+    count
+}
+
+fn parse_rules (mut rules: Vec<String>) -> HashMap<String, String> {
+    let mut parsed: HashMap<String, String> = HashMap::new();
+    while parsed.len() < 2 {
+        for i in 0..rules.len() {
+            // pre populate the a and b
+            if rules[i].contains("a") || rules[i].contains("b") {
+                let rule = rules[i].split(": ").collect::<Vec<&str>>();
+                parsed.insert(rule[0].to_owned(), rule[1].to_owned());
+                // remove them so we don't try to process them again later
+                rules.remove(i);
+                break;
+            }
+        }
+    }
+    while let Some(popped) = rules.pop() {
+        
+        let rule = popped.split(": ").collect::<Vec<&str>>();
+
+        // initialise the parsed rule so we can append stuff to it
+        let mut p_rule = "".to_owned();
+        // 12 15 | 10 5 => [12, 15, |, 10, 5]
+        let subs = rule[1].split(" ").collect::<Vec<&str>>();
+        for (i, sub) in subs.iter().enumerate() {
+            if sub == &"|" {
+                p_rule.push_str(sub);
+                continue;
+            }
+
+            if sub == &rule[0] {
+                continue;
+            }
+            // handle part 2
+            if sub == &"{1,}" || sub == &"{N}" {
+                p_rule.push_str(sub);
+                // check if we've processed all the sub rules
+                if i == subs.len() - 1 {
+                    p_rule.push(')');
+                    p_rule.insert(0, '(');
+                    parsed.insert(rule[0].into(), p_rule.clone());
+                }
+            } else {
+                // can we parse this subrule?
+                match parsed.get(*sub) {
+                    Some(val) => {
+                        p_rule.push_str(val);
+                        // check if we've processed all the sub rules
+                        if i == subs.len() - 1 {
+                            p_rule.push(')');
+                            p_rule.insert(0, '(');
+                            parsed.insert(rule[0].into(), p_rule.clone());
+                        }
+                    },
+                    None => {
+                        // put unfinished rule back into the stack;
+                        rules.insert(0, popped);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    parsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_test() {
+        let rules = vec![
+            "0: 4 1 5".to_owned(),
+            "1: 2 3 | 3 2".to_owned(),
+            "2: 4 4 | 5 5".to_owned(),
+            "3: 4 5 | 5 4".to_owned(),
+            "4: a".to_owned(),
+            "5: b".to_owned()
+        ];
+        let messages = vec![
+            "ababbb",
+            "bababa",
+            "abbbab",
+            "aaabbb",
+            "aaaabbb"
+        ];
+        let messages = messages.into_iter().map(String::from).collect();
+        assert_eq!(part_1(&(rules, messages)), 2);
+    }
+
+    #[test]
+    fn part_2_test() {
+        let rules = vec![
+            "42: 9 14 | 10 1".to_owned(),
+            "9: 14 27 | 1 26".to_owned(),
+            "10: 23 14 | 28 1".to_owned(),
+            "1: a".to_owned(),
+            "11: 42 31".to_owned(),
+            "5: 1 14 | 15 1".to_owned(),
+            "19: 14 1 | 14 14".to_owned(),
+            "12: 24 14 | 19 1".to_owned(),
+            "16: 15 1 | 14 14".to_owned(),
+            "31: 14 17 | 1 13".to_owned(),
+            "6: 14 14 | 1 14".to_owned(),
+            "2: 1 24 | 14 4".to_owned(),
+            "0: 8 11".to_owned(),
+            "13: 14 3 | 1 12".to_owned(),
+            "15: 1 | 14".to_owned(),
+            "17: 14 2 | 1 7".to_owned(),
+            "23: 25 1 | 22 14".to_owned(),
+            "28: 16 1".to_owned(),
+            "4: 1 1".to_owned(),
+            "20: 14 14 | 1 15".to_owned(),
+            "3: 5 14 | 16 1".to_owned(),
+            "27: 1 6 | 14 18".to_owned(),
+            "14: b".to_owned(),
+            "21: 14 1 | 1 14".to_owned(),
+            "25: 1 1 | 1 14".to_owned(),
+            "22: 14 14".to_owned(),
+            "8: 42".to_owned(),
+            "26: 14 22 | 1 20".to_owned(),
+            "18: 15 15".to_owned(),
+            "7: 14 5 | 1 21".to_owned(),
+            "24: 14 1".to_owned(),
+        ];
+        let messages = vec![
+            "abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa",
+            "bbabbbbaabaabba",
+            "babbbbaabbbbbabbbbbbaabaaabaaa",
+            "aaabbbbbbaaaabaababaabababbabaaabbababababaaa",
+            "bbbbbbbaaaabbbbaaabbabaaa",
+            "bbbababbbbaaaaaaaabbababaaababaabab",
+            "ababaaaaaabaaab",
+            "ababaaaaabbbaba",
+            "baabbaaaabbaaaababbaababb",
+            "abbbbabbbbaaaababbbbbbaaaababb",
+            "aaaaabbaabaaaaababaa",
+            "aaaabbaaaabbaaa",
+            "aaaabbaabbaaaaaaabbbabbbaaabbaabaaa",
+            "babaaabbbaaabaababbaabababaaab",
+            "aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba",
+        ];
+        let messages = messages.into_iter().map(String::from).collect();
+        assert_eq!(part_2(&(rules, messages)), 12);
+    }
+}
@@ -0,0 +1,383 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use common::Answers;
+
+/// The `1-3 a` half of a line, read differently by different policies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Policy {
+    pub min: u32,
+    pub max: u32,
+    pub letter: char,
+}
+
+/// Decides whether a password is acceptable.
+pub trait PasswordPolicy {
+    /// `Err` says why `password` breaks the policy.
+    fn check(&self, password: &str) -> Result<(), String>;
+}
+
+/// The letter appears between `min` and `max` times.
+pub struct LetterCount(pub Policy);
+
+impl PasswordPolicy for LetterCount {
+    fn check(&self, password: &str) -> Result<(), String> {
+        let Policy { min, max, letter } = self.0;
+        let count = password.chars().filter(|c| *c == letter).count() as u32;
+        match count >= min && count <= max {
+            true => Ok(()),
+            false => Err(format!("expected {} to {} '{}', found {}", min, max, letter, count)),
+        }
+    }
+}
+
+/// The letter is at exactly one of the 1-based positions `min` and `max`.
+pub struct LetterPositions(pub Policy);
+
+impl PasswordPolicy for LetterPositions {
+    fn check(&self, password: &str) -> Result<(), String> {
+        let Policy { min, max, letter } = self.0;
+        let at = |position: u32| position > 0 && password.chars().nth(position as usize - 1) == Some(letter);
+        match (at(min), at(max)) {
+            (true, false) | (false, true) => Ok(()),
+            (true, true) => Err(format!("expected '{}' at one of positions {} and {}, found it at both", letter, min, max)),
+            (false, false) => Err(format!("expected '{}' at one of positions {} and {}, found it at neither", letter, min, max)),
+        }
+    }
+}
+
+/// None of the letters appear.
+pub struct ForbiddenLetters(pub Vec<char>);
+
+impl PasswordPolicy for ForbiddenLetters {
+    fn check(&self, password: &str) -> Result<(), String> {
+        match password.chars().enumerate().find(|(_, c)| self.0.contains(c)) {
+            Some((i, c)) => Err(format!("found forbidden '{}' at position {}", c, i + 1)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Every one of several letters appears within its own range.
+pub struct LetterCounts(pub Vec<Policy>);
+
+impl PasswordPolicy for LetterCounts {
+    fn check(&self, password: &str) -> Result<(), String> {
+        let reasons = self.0.iter()
+                            .filter_map(|policy| LetterCount(policy.clone()).check(password).err())
+                            .collect::<Vec<String>>();
+        match reasons.is_empty() {
+            true => Ok(()),
+            false => Err(reasons.join("; ")),
+        }
+    }
+}
+
+/// Builds a policy from the text before the `: ` on a line, or says why it can't.
+pub type Kind = Box<dyn Fn(&str) -> Result<Box<dyn PasswordPolicy>, String>>;
+
+/// Policy kinds by name. Starts out with the two from the puzzle, `count` and
+/// `position`, plus `forbidden` (`xyz: password`) and `letters`
+/// (`1-3 a, 2-4 b: password`).
+pub struct Registry {
+    kinds: HashMap<String, Kind>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        let mut registry = Registry { kinds: HashMap::new() };
+        registry.register("count", |spec| Ok(Box::new(LetterCount(read_policy(spec)?))));
+        registry.register("position", |spec| Ok(Box::new(LetterPositions(read_policy(spec)?))));
+        registry.register("forbidden", |spec| {
+            match !spec.is_empty() && spec.chars().all(|c| c.is_alphabetic()) {
+                true => Ok(Box::new(ForbiddenLetters(spec.chars().collect()))),
+                false => Err(format!("expected letters, found '{}'", spec)),
+            }
+        });
+        registry.register("letters", |spec| {
+            let policies = spec.split(", ").map(read_policy).collect::<Result<Vec<Policy>, String>>()?;
+            Ok(Box::new(LetterCounts(policies)))
+        });
+        registry
+    }
+
+    /// Adds a kind of policy, replacing any with the same name.
+    pub fn register(&mut self, name: &str, kind: impl Fn(&str) -> Result<Box<dyn PasswordPolicy>, String> + 'static) {
+        self.kinds.insert(name.to_owned(), Box::new(kind));
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names = self.kinds.keys().map(|name| name.as_str()).collect::<Vec<&str>>();
+        names.sort_unstable();
+        names
+    }
+
+    /// Checks every `<policy>: <password>` line of `input` against the `name`d
+    /// kind of policy. Lines whose policy doesn't make sense for the kind fail too.
+    pub fn report(&self, name: &str, input: &str) -> Result<Report, String> {
+        let kind = self.kinds.get(name).ok_or_else(|| {
+            format!("unknown policy '{}', expected one of {}", name, self.names().join(", "))
+        })?;
+        let mut report = Report { checked: 0, failures: Vec::new() };
+        for (i, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            report.checked += 1;
+            let result = match line.split_once(": ") {
+                Some((spec, password)) => kind(spec).and_then(|policy| policy.check(password)),
+                None => Err("expected '<policy>: <password>'".to_owned()),
+            };
+            if let Err(reason) = result {
+                report.failures.push(Failure { line: i + 1, entry: line.to_owned(), reason });
+            }
+        }
+        Ok(report)
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Registry {
+        Registry::new()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    pub line: usize,
+    pub entry: String,
+    pub reason: String,
+}
+
+/// Every line that broke its policy, and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub checked: usize,
+    pub failures: Vec<Failure>,
+}
+
+impl Report {
+    pub fn valid(&self) -> usize {
+        self.checked - self.failures.len()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for failure in &self.failures {
+            writeln!(f, "line {}: '{}': {}", failure.line, failure.entry, failure.reason)?;
+        }
+        write!(f, "{} of {} passwords valid", self.valid(), self.checked)
+    }
+}
+
+/// One line of the puzzle input, read the way both parts read it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub policy: Policy,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Number,
+    Dash,
+    Space,
+    Letter,
+    Separator,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Number => write!(f, "a number"),
+            Expected::Dash => write!(f, "'-'"),
+            Expected::Space => write!(f, "' '"),
+            Expected::Letter => write!(f, "a single letter"),
+            Expected::Separator => write!(f, "': '"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Vec<Entry>;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    input.lines()
+         .map(|entry| {
+             let (policy, password) = entry.split_once(": ")
+                                           .ok_or_else(|| ParseError::at(input, &entry[entry.len()..], Expected::Separator))?;
+             Ok(Entry {
+                 policy: parse_policy(input, policy)?,
+                 password: password.to_owned(),
+             })
+         })
+         .collect::<Result<Vec<Entry>, ParseError>>()
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let entries = parse(input)?;
+    Ok(Answers::new(part_1(&entries), part_2(&entries)))
+}
+
+// a policy on its own, for kinds that read one
+fn read_policy(spec: &str) -> Result<Policy, String> {
+    parse_policy(spec, spec).map_err(|err| format!("expected {}, found '{}'", err.expected, err.found))
+}
+
+fn parse_policy(input: &str, policy: &str) -> Result<Policy, ParseError> {
+    let (min, rest) = policy.split_once('-')
+                            .ok_or_else(|| ParseError::at(input, policy, Expected::Dash))?;
+    let (max, letter) = rest.split_once(' ')
+                            .ok_or_else(|| ParseError::at(input, rest, Expected::Space))?;
+    let min: u32 = min.parse::<u32>().map_err(|_| ParseError::at(input, min, Expected::Number))?;
+    let max: u32 = max.parse::<u32>().map_err(|_| ParseError::at(input, max, Expected::Number))?;
+    let letter: char = letter.parse::<char>().map_err(|_| ParseError::at(input, letter, Expected::Letter))?;
+
+    Ok(Policy {
+        min,
+        max,
+        letter
+    })
+}
+
+fn is_valid_password(password: &str, policy: &Policy) -> bool {
+    LetterPositions(policy.clone()).check(password).is_ok()
+}
+
+pub fn part_1(entries: &[Entry]) -> u32 {
+    entries.iter()
+           .filter(|entry| LetterCount(entry.policy.clone()).check(&entry.password).is_ok())
+           .count() as u32
+}
+
+pub fn part_2(entries: &[Entry]) -> u32 {
+    entries.iter()
+           .filter(|entry| is_valid_password(&entry.password, &entry.policy))
+           .count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
+    fn valid_password_tests() {
+        let password1: &str = "abcdefg";
+        let password2: &str = "aaaaaaa";
+        let password3: &str = "aaaabbb";
+        assert!(is_valid_password(password1, &Policy {
+            min: 1,
+            max: 3,
+            letter: "a".parse::<char>().unwrap()
+        }));
+
+        assert_eq!(is_valid_password(password1, &Policy {
+            min: 2,
+            max: 3,
+            letter: "a".parse::<char>().unwrap()
+        }), false);
+
+        assert_eq!(is_valid_password(password2, &Policy {
+            min: 1,
+            max: 3,
+            letter: "a".parse::<char>().unwrap()
+        }), false);
+
+        assert!(is_valid_password(password3, &Policy {
+            min: 2,
+            max: 5,
+            letter: "a".parse::<char>().unwrap()
+        }));
+
+        assert_eq!(is_valid_password(password3, &Policy {
+            min: 1,
+            max: 2,
+            letter: "a".parse::<char>().unwrap()
+        }), false);
+    }
+
+    #[test]
+    #[allow(clippy::vec_init_then_push)]
+    fn part_2_tests() {
+        let mut vec = Vec::new();
+        vec.push("1-3 a: abcde");
+        vec.push("1-3 b: cdefg");
+        vec.push("2-9 c: cccccccccc");
+        assert_eq!(part_2(&parse(&vec.join("\n")).unwrap()), 1);
+    }
+
+    #[test]
+    fn parse_error_tests() {
+        let err = parse("1-3 a: abcde\n\
+                         1-x b: cdefg").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected.clone()), (2, 3, Expected::Number));
+        assert_eq!(err.to_string(), "line 2, column 3: expected a number, found 'x'");
+
+        let err = parse("1-3 a abcde").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (1, 12, Expected::Separator));
+
+        let err = parse("1-3 ab: abcde").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (1, 5, Expected::Letter));
+    }
+
+    #[test]
+    fn report_tests() {
+        let input = "1-3 a: abcde\n\
+                     1-3 b: cdefg\n\
+                     2-9 c: ccccccccc";
+        let registry = Registry::new();
+
+        let report = registry.report("count", input).unwrap();
+        assert_eq!(report.valid(), 2);
+        assert_eq!(report.failures, vec![Failure {
+            line: 2,
+            entry: "1-3 b: cdefg".to_owned(),
+            reason: "expected 1 to 3 'b', found 0".to_owned(),
+        }]);
+
+        let report = registry.report("position", input).unwrap();
+        assert_eq!(report.valid(), 1);
+        assert_eq!(report.failures[1].reason, "expected 'c' at one of positions 2 and 9, found it at both");
+        assert_eq!(report.to_string(), "line 2: '1-3 b: cdefg': expected 'b' at one of positions 1 and 3, found it at neither\n\
+                                        line 3: '2-9 c: ccccccccc': expected 'c' at one of positions 2 and 9, found it at both\n\
+                                        1 of 3 passwords valid");
+
+        let report = registry.report("forbidden", "xz: abcde\nxz: abxde\n1-3 a: abc").unwrap();
+        assert_eq!(report.failures.iter().map(|f| f.reason.as_str()).collect::<Vec<&str>>(),
+                   vec!["found forbidden 'x' at position 3", "expected letters, found '1-3 a'"]);
+
+        let report = registry.report("letters", "1-2 a, 1-1 b: aab\n1-1 a, 2-3 b: aab\n1-2 a: abc").unwrap();
+        assert_eq!(report.failures.iter().map(|f| f.reason.as_str()).collect::<Vec<&str>>(),
+                   vec!["expected 1 to 1 'a', found 2; expected 2 to 3 'b', found 1"]);
+
+        let report = registry.report("count", "1-x a: abc\nno separator").unwrap();
+        assert_eq!(report.failures.iter().map(|f| f.reason.as_str()).collect::<Vec<&str>>(),
+                   vec!["expected a number, found 'x'", "expected '<policy>: <password>'"]);
+
+        let mut registry = Registry::new();
+        struct MinLength(usize);
+        impl PasswordPolicy for MinLength {
+            fn check(&self, password: &str) -> Result<(), String> {
+                match password.len() >= self.0 {
+                    true => Ok(()),
+                    false => Err(format!("expected at least {} characters", self.0)),
+                }
+            }
+        }
+        registry.register("length", |spec| {
+            spec.parse::<usize>().map(|n| Box::new(MinLength(n)) as Box<dyn PasswordPolicy>).map_err(|_| "expected a length".to_owned())
+        });
+        assert_eq!(registry.names(), vec!["count", "forbidden", "length", "letters", "position"]);
+        assert_eq!(registry.report("length", "3: abcd\n5: abcd").unwrap().failures.len(), 1);
+        assert_eq!(registry.report("missing", input).err().unwrap(),
+                   "unknown policy 'missing', expected one of count, forbidden, length, letters, position");
+
+        // entries built outside the parser check against any policy
+        let entries = vec![Entry { policy: Policy { min: 1, max: 2, letter: 'z' }, password: "zoo".to_owned() }];
+        assert_eq!(part_1(&entries), 1);
+        assert!(MinLength(4).check(&entries[0].password).is_err());
+    }
+}
@@ -0,0 +1,401 @@
+use std::fmt;
+
+use common::{Answers, Grid};
+
+#[derive(Clone)]
+pub struct Tile {
+    id: u64,
+    map: Grid<char>
+}
+
+struct Sides {
+    top: String,
+    bottom: String,
+    left: String,
+    right: String
+}
+
+struct Join {
+    y: i32,
+    x: i32
+}
+
+impl Tile {
+    fn left(&self) -> String {
+        self.map.column(0).collect::<String>()
+    }
+    fn right(&self) -> String {
+        self.map.column(self.map.width() - 1).collect::<String>()
+    }
+    fn top(&self) -> String {
+        self.map.row(0).iter().collect::<String>()
+    }
+    fn bottom(&self) -> String {
+        self.map.row(self.map.height() - 1).iter().collect::<String>()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Header,
+    Pixel,
+    EndOfRow,
+    Row,
+    EndOfTile,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Header => write!(f, "'Tile <id>:'"),
+            Expected::Pixel => write!(f, "'.' or '#'"),
+            Expected::EndOfRow => write!(f, "the end of the row"),
+            Expected::Row => write!(f, "another row of the tile"),
+            Expected::EndOfTile => write!(f, "the end of the tile"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Vec<Tile>;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    input.trim()
+         .split("\n\n")
+         .map(|t| {
+             let tile = t.trim().split("\n").collect::<Vec<&str>>();
+             let id = tile[0].strip_prefix("Tile ")
+                             .and_then(|id| id.strip_suffix(':'))
+                             .and_then(|id| id.parse::<u64>().ok())
+                             .ok_or_else(|| ParseError::at(input, tile[0], Expected::Header))?;
+             if tile.len() == 1 {
+                 return Err(ParseError::at(input, &tile[0][tile[0].len()..], Expected::Row));
+             }
+             Ok(Tile {
+                 id,
+                 map: build_map(input, &tile[1..])?
+             })
+         })
+         .collect::<Result<Vec<Tile>, ParseError>>()
+}
+
+// tiles are square so they can be rotated
+fn build_map(input: &str, rows: &[&str]) -> Result<Grid<char>, ParseError> {
+    let size = rows.first().map_or(0, |row| row.len());
+    let pixel = |c| match c {
+        '.' | '#' => Some(c),
+        _ => None,
+    };
+    let map = Grid::parse_rows(input, &rows[..rows.len().min(size)], pixel, Expected::Pixel, Expected::EndOfRow)?;
+    if rows.len() > size {
+        return Err(ParseError::at(input, rows[size], Expected::EndOfTile));
+    }
+    if rows.len() < size {
+        let last = rows[rows.len() - 1];
+        return Err(ParseError::at(input, &last[last.len()..], Expected::Row));
+    }
+    Ok(map)
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let tiles = parse(input)?;
+    let (corners, grid) = assemble(tiles);
+    Ok(Answers::new(corners, roughness(grid)))
+}
+
+pub fn part_1(tiles: &[Tile]) -> u64 {
+    assemble(tiles.to_vec()).0
+}
+
+pub fn part_2(tiles: &[Tile]) -> u32 {
+    roughness(assemble(tiles.to_vec()).1)
+}
+
+fn monster() -> Grid<char> {
+    Grid::from_rows(vec![
+        "                  # ".chars().collect::<Vec<char>>(),
+        "#    ##    ##    ###".chars().collect::<Vec<char>>(),
+        " #  #  #  #  #  #   ".chars().collect::<Vec<char>>()
+    ])
+}
+
+fn roughness(grid: Vec<Vec<Tile>>) -> u32 {
+    let image = flatten_and_trim(grid);
+    let monster = monster();
+    let monster_count = count_hashes(&monster);
+    let image_count = count_hashes(&image);
+    let monsters = count_monsters(image, &monster);
+
+    image_count - (monster_count * monsters)
+}
+
+fn count_hashes(map: &Grid<char>) -> u32 {
+    map.iter().filter(|c| **c == '#').count() as u32
+}
+
+fn count_monsters(mut image: Grid<char>, monster: &Grid<char>) -> u32 {
+    let mut count: u32 = 0;
+
+    for _i in 1..=2 {
+        for _j in 1..=4 {
+            count += find_monsters(&image, monster);
+            image = image.rotate();
+        }
+        image = image.flip();
+    }
+
+    count
+}
+
+fn find_monsters(image: &Grid<char>, monster: &Grid<char>) -> u32 {
+    let mut count = 0;
+
+    // until the monster touches the bottom right corner
+    for y in 0..=image.height() - monster.height() {
+        for x in 0..=image.width() - monster.width() {
+            let is_monster = monster.cells()
+                .filter(|(_, c)| **c == '#')
+                .all(|((mx, my), c)| image[(x + mx, y + my)] == *c);
+            if is_monster {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+// drops every tile's border and stitches what's left into one image
+fn flatten_and_trim(grid: Vec<Vec<Tile>>) -> Grid<char> {
+    let inner = grid[0][0].map.width() - 2;
+    Grid::from_fn(grid[0].len() * inner, grid.len() * inner, |x, y| {
+        grid[y / inner][x / inner].map[(x % inner + 1, y % inner + 1)]
+    })
+}
+
+fn assemble(mut tiles: Vec<Tile>) -> (u64, Vec<Vec<Tile>>) {
+    let len = (tiles.len() as f64).sqrt() as usize;
+
+    let mut grid: Vec<Vec<Tile>> = vec![vec![empty_grid(tiles[0].map.width()); len * 3]; len * 3];
+    let mut tile_coords: Vec<(usize, usize)> = Vec::new();
+
+    let mut first = tiles.pop().unwrap();
+
+    first = rotate_tile(first);
+    first = flip_tile(first);
+    first = rotate_tile(first);
+    first = flip_tile(first);
+
+    grid[len][len] = first;
+    tile_coords.push((len, len));
+
+    while let Some(t) = tiles.pop() {
+        
+        let mut matched = false;
+        for i in 0..tile_coords.len() {
+            let tile = &grid[tile_coords[i].0][tile_coords[i].1];
+            if let Some((t, join)) = find_join(tile.clone(), t.clone()) {
+                let x = (tile_coords[i].1 as i32 + join.x) as usize;
+                let y = (tile_coords[i].0 as i32 + join.y) as usize;
+                if !tile_coords.contains(&(y, x)) {
+                    grid[y][x] = t;
+                    tile_coords.push((y, x));
+                    matched = true;
+                    break;
+                }
+            }
+        }
+        if !matched {
+            tiles.insert(0, t.clone());
+        }
+    }
+    let grid = clear_whitespace(grid, len);
+
+    (grid[0][0].id * grid[0][len - 1].id * grid[len - 1][0].id * grid[len - 1][len - 1].id, grid)
+}
+
+fn clear_whitespace(mut grid: Vec<Vec<Tile>>, len: usize) -> Vec<Vec<Tile>> {
+    let mut new_grid: Vec<Vec<Tile>> = Vec::new();
+    while let Some(mut line) = grid.pop() {
+        
+        loop {
+            if line.len() == len {
+                if line[0].map[(0, 0)] != ' ' {
+                    new_grid.push(line);
+                }
+                break
+            }
+            for i in 0..line.len() {
+                if line[i].map[(0, 0)] == ' ' {
+                    line.remove(i);
+                    break;
+                }
+            }
+        }
+    }
+
+    new_grid
+}
+
+fn find_join(tile1: Tile, mut tile2: Tile) -> Option<(Tile, Join)> {
+    let sides = Sides { top: tile1.top(), bottom: tile1.bottom(), left: tile1.left(), right: tile1.right() };
+    for _i in 1..=2 {
+        for _j in 1..=4 {
+            if tile2.bottom() == sides.top {
+                let join = Join { x: 0, y: 1 };
+                return Some((tile2, join))
+            }
+            if tile2.top() == sides.bottom {
+                let join = Join{ x: 0, y: -1 };
+                return Some((tile2, join))
+            }
+            if tile2.right() == sides.left {
+                let join = Join{ x: -1, y: 0 };
+                return Some((tile2, join))
+            }
+            if tile2.left() == sides.right {
+                let join = Join{ x: 1, y: 0 };
+                return Some((tile2, join))
+            }
+            tile2 = rotate_tile(tile2);
+        }
+        tile2 = flip_tile(tile2);
+    }
+    None
+}
+
+fn rotate_tile(tile: Tile) -> Tile {
+    Tile {
+        id: tile.id,
+        map: tile.map.rotate()
+    }
+}
+
+fn flip_tile(tile: Tile) -> Tile {
+    Tile {
+        id: tile.id,
+        map: tile.map.flip()
+    }
+}
+
+fn empty_grid(len: usize) -> Tile {
+    Tile {
+        id: 0,
+        map: Grid::filled(len, len, ' ')
+    }
+}
+
+fn _print_tile(tile: &Tile) {
+    println!("{}", tile.map);
+    println!();
+}
+
+#[allow(clippy::needless_range_loop)]
+fn _print_grid(grid: &[Vec<Tile>]) {
+    let mut buffer: Vec<Vec<String>> = vec![vec!["".to_owned(); grid[0][0].map.height()]; grid[0].len()];
+    for k in 0..grid[0][0].map.height() {
+        for i in 0..grid.len() {
+            for j in 0..grid[0].len() {
+                buffer[i][k].push_str(grid[i][j].map.row(k).iter().collect::<String>().as_str());
+                buffer[i][k].push(' ');
+            }
+        }
+    }
+    for set in buffer {
+        for line in set {
+            println!("{}", line);
+        }
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test_input.txt");
+
+    #[test]
+    fn part_2_test() {
+        let expected_map = ".#.#..#.##...#.##..#####\n\
+                            ###....#.#....#..#......\n\
+                            ##.##.###.#.#..######...\n\
+                            ###.#####...#.#####.#..#\n\
+                            ##.#....#.##.####...#.##\n\
+                            ...########.#....#####.#\n\
+                            ....#..#...##..#.#.###..\n\
+                            .####...#..#.....#......\n\
+                            #..#.##..#..###.#.##....\n\
+                            #.####..#.####.#.#.###..\n\
+                            ###.#.#...#.######.#..##\n\
+                            #.####....##..########.#\n\
+                            ##..##.#...#...#.#.#.#..\n\
+                            ...#..#..#.#.##..###.###\n\
+                            .#.#....#.##.#...###.##.\n\
+                            ###.#...#..#.##.######..\n\
+                            .#.#.###.##.##.#..#.##..\n\
+                            .####.###.#...###.#..#.#\n\
+                            ..#.#..#..#.#.#.####.###\n\
+                            #..####...#.#.#.###.###.\n\
+                            #####..#####...###....##\n\
+                            #.##..#..#...#..####...#\n\
+                            .#.###..##..##..####.##.\n\
+                            ...###...##...#...#..###";
+
+        let tiles = parse(EXAMPLE).unwrap();
+        let grid = assemble(tiles).1;
+
+        assert_eq!(flatten_and_trim(grid.clone()).to_string(), expected_map);
+
+        let monster = monster();
+        assert_eq!(find_monsters(&monster, &monster), 1);
+
+        assert_eq!(roughness(grid), 273);
+
+    }
+
+    #[test]
+    fn part_1_tests() {
+        let tiles = parse(EXAMPLE).unwrap();
+        tiles_test();
+
+        assert_eq!(part_1(&tiles), 20899048083289);
+    }
+
+    #[test]
+    fn tiles_test() {
+        let map ="#.#.#####.\n\
+                  .#..######\n\
+                  ..#.......\n\
+                  ######....\n\
+                  ####.#..#.\n\
+                  .#...#.##.\n\
+                  #.#####.##\n\
+                  ..#.###...\n\
+                  ..#.......\n\
+                  ..#.###...";
+
+        let tile = Tile {
+            id: 1,
+            map: Grid::from_rows(map.split("\n").map(|line| line.chars().collect::<Vec<char>>()).collect())
+        };
+        assert_eq!(tile.left(), "#..##.#...");
+        assert_eq!(tile.right(), ".#....#...");
+        assert_eq!(tile.top(), "#.#.#####.");
+        assert_eq!(tile.bottom(), "..#.###...");
+
+        let tile = rotate_tile(tile);
+
+        assert_eq!(tile.left(), "..#.###...");
+        assert_eq!(tile.right(), "#.#.#####.");
+        assert_eq!(tile.top(), "...#.##..#");
+        assert_eq!(tile.bottom(), "...#....#.");
+
+        let tile = flip_tile(tile);
+
+        assert_eq!(tile.top(), "#..##.#...");
+        assert_eq!(tile.bottom(), ".#....#...");
+        assert_eq!(tile.right(), "..#.###...");
+        assert_eq!(tile.left(), "#.#.#####.");
+    }
+}
@@ -0,0 +1,200 @@
+use common::{Answers};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Clone)]
+pub struct Food {
+    ingredients: Vec<String>,
+    allergens: Vec<String>
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Ingredient,
+    Contains,
+    Allergen,
+    CloseParen,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Ingredient => write!(f, "an ingredient"),
+            Expected::Contains => write!(f, "' (contains '"),
+            Expected::Allergen => write!(f, "an allergen"),
+            Expected::CloseParen => write!(f, "')'"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Vec<Food>;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    input.trim()
+         .split("\n")
+         .map(|food| build_food(input, food))
+         .collect::<Result<Vec<Food>, ParseError>>()
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let foods = parse(input)?;
+    let (count, allergens) = translate(&foods);
+    Ok(Answers::new(count, dangerous_ingredients(allergens)))
+}
+
+pub fn part_1(foods: &[Food]) -> u32 {
+    translate(foods).0
+}
+
+pub fn part_2(foods: &[Food]) -> String {
+    dangerous_ingredients(translate(foods).1)
+}
+
+fn dangerous_ingredients(allergens: HashMap<String, String>) -> String {
+    let mut list = "".to_owned();
+
+    let mut sorted_allergens = allergens.keys().cloned().collect::<Vec<String>>();
+    sorted_allergens.sort();
+    for a in sorted_allergens {
+        if let Some(ingred) = allergens.get(&a) {
+            list.push_str(ingred.as_str());
+            list.push(',');
+        }
+    }
+    list
+}
+
+fn translate(foods: &[Food]) -> (u32, HashMap<String, String>) {
+    let (allergen_ingredients, mut translated_allergens) = build_candidates(foods);
+    let mut allergens: Vec<String> = allergen_ingredients.keys().cloned().collect::<Vec<String>>();
+
+    while let Some(allergen) = allergens.pop() {
+        
+        match translated_allergens.get(&allergen) {
+            Some(_) => continue,
+            None => {
+                if let Some(ingreds) = allergen_ingredients.get(&allergen) {
+                    let mut filtered = ingreds.clone();
+                    for t in translated_allergens.values() {
+                        if let Some(i) = filtered.iter().position(|i| i == t) {
+                            filtered.remove(i);
+                        }
+                    }
+                    if filtered.len() == 1 {
+                        translated_allergens.insert(allergen, filtered[0].clone());
+                    } else {
+                        allergens.insert(0, allergen);
+                    }
+                }
+            }
+        }
+    }
+
+    let ingredients = list_ingredients(foods);
+    let mut count = 0;
+    let allergens = translated_allergens.values().cloned().collect::<Vec<String>>();
+
+    for i in ingredients {
+        if allergens.contains(i) {
+            continue;
+        }
+        count += 1;
+    }
+    (count, translated_allergens)
+}
+
+fn list_ingredients(foods: &[Food]) -> Vec<&String> {
+    let mut ingredients: Vec<&String> = Vec::new();
+    for food in foods {
+        for ingredient in &food.ingredients {
+            ingredients.push(ingredient);
+        }
+    }
+    ingredients
+}
+
+fn build_candidates(foods: &[Food]) -> (HashMap<String, Vec<String>>, HashMap<String, String>) {
+    let mut allergen_ingredients: HashMap<String, Vec<String>> = HashMap::new();
+    let mut translated_allergens: HashMap<String, String> = HashMap::new();
+    for food in foods {
+        for allergen in &food.allergens {
+            match allergen_ingredients.get(allergen) {
+                Some(v) => {
+                    let ingredients = reduce(v, &food.ingredients);
+                    if ingredients.len() > 1 {
+                        allergen_ingredients.insert(allergen.clone(), ingredients);
+                    } else {
+                        allergen_ingredients.remove(allergen);
+                        translated_allergens.insert(allergen.clone(), ingredients[0].clone());
+                    }
+                },
+                None => {
+                    allergen_ingredients.insert(allergen.clone(), food.ingredients.clone());
+                }
+            }
+        }
+    }
+    (allergen_ingredients, translated_allergens)
+}
+
+fn reduce(left: &[String], right: &[String]) -> Vec<String> {
+    let mut reduced: Vec<String> = Vec::new();
+    for val in left {
+        if right.contains(val) {
+            reduced.push(val.clone());
+        }
+    }
+    reduced
+}
+
+fn build_food(input: &str, string: &str) -> Result<Food, ParseError> {
+    let (ingredients, allergens) = string.split_once(" (contains ")
+                                         .ok_or_else(|| ParseError::at(input, &string[string.len()..], Expected::Contains))?;
+    let allergens = allergens.strip_suffix(')')
+                             .ok_or_else(|| ParseError::at(input, &allergens[allergens.len()..], Expected::CloseParen))?;
+
+    Ok(Food {
+        ingredients: build_names(input, ingredients.split(" "), Expected::Ingredient)?,
+        allergens: build_names(input, allergens.split(", "), Expected::Allergen)?
+    })
+}
+
+fn build_names<'a>(input: &str, names: impl Iterator<Item = &'a str>, expected: Expected) -> Result<Vec<String>, ParseError> {
+    names.map(|name| match name.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+             Some((i, _)) => Err(ParseError::at(input, &name[i..], expected.clone())),
+             None if name.is_empty() => Err(ParseError::at(input, name, expected.clone())),
+             None => Ok(name.to_owned()),
+         })
+         .collect::<Result<Vec<String>, ParseError>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test_input.txt");
+
+    #[test]
+    fn part_1_test() {
+        let foods = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&foods), 5);
+    }
+
+    #[test]
+    fn parse_error_tests() {
+        let err = parse("mxmxvkd kfcds (contains dairy, fish)\n\
+                         trh fvjkl sbzzf mxmxvkd (contains dairy").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (2, 40, Expected::CloseParen));
+
+        let err = parse("mxmxvkd kfcds contains dairy").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (1, 29, Expected::Contains));
+
+        let err = parse("mxmxvkd  kfcds (contains dairy, Fish)").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (1, 9, Expected::Ingredient));
+
+        let err = parse("mxmxvkd kfcds (contains dairy, Fish)").err().unwrap();
+        assert_eq!(err.to_string(), "line 1, column 32: expected an allergen, found 'Fish'");
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use common::Answers;
+
+pub type Deck = Vec<u32>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Player,
+    Card,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Player => write!(f, "'Player 1:' or 'Player 2:'"),
+            Expected::Card => write!(f, "a card number"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = (Deck, Deck);
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    let decks = input.trim()
+                        .split("\n\n")
+                        .collect::<Vec<&str>>();
+    let player_2 = decks.get(1).copied().unwrap_or(&input[input.len()..]);
+
+    Ok((build_deck(input, decks[0], "Player 1:")?, build_deck(input, player_2, "Player 2:")?))
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let decks = parse(input)?;
+    Ok(Answers::new(part_1(&decks), part_2(&decks)))
+}
+
+pub fn part_2((p1, p2): &Parsed) -> u32 {
+    let (p1, p2) = new_game(p1.clone(), p2.clone());
+
+    calculate_winner(p1, p2)
+}
+
+fn new_game(mut p1: Deck, mut p2: Deck) -> (Deck, Deck) {
+    let mut history = HashSet::new();
+
+    while !p1.is_empty() && !p2.is_empty() {
+        if !history.insert((p1.clone(), p2.clone())) {
+            while let Some(element) = p2.pop() {
+                p1.insert(0, element);
+            }
+            break
+        }
+
+        let p1c = p1.pop().unwrap();
+        let p2c = p2.pop().unwrap();
+
+        let mut p1_win = winner(p1c, p2c);
+
+        if p1.len() as u32 >= p1c && p2.len() as u32 >= p2c {
+            let p1_sub = trim(p1.clone(), p1c);
+            let p2_sub = trim(p2.clone(), p2c);
+            let (p1_new, p2_new) = new_game(p1_sub, p2_sub);
+
+            p1_win = p1_new.len() > p2_new.len();
+
+        }
+
+        if p1_win {
+            p1.insert(0, p1c);
+            p1.insert(0, p2c);
+        } else {
+            p2.insert(0, p2c);
+            p2.insert(0, p1c);
+        }
+    }
+
+    (p1, p2)
+}
+
+fn winner(p1: u32, p2: u32) -> bool {
+    p1 > p2
+}
+
+fn trim(mut deck: Deck, len: u32) -> Deck {
+    while deck.len() > len as usize {
+        deck.remove(0);
+    }
+    deck
+}
+
+pub fn part_1((p1, p2): &Parsed) -> u32 {
+    let mut p1 = p1.clone();
+    let mut p2 = p2.clone();
+    while !p1.is_empty() && !p2.is_empty() {
+        let p1_card = p1.pop().unwrap();
+        let p2_card = p2.pop().unwrap();
+        if p1_card > p2_card {
+            p1.insert(0, p1_card);
+            p1.insert(0, p2_card);
+        } else {
+            p2.insert(0, p2_card);
+            p2.insert(0, p1_card);
+        }
+    }
+    calculate_winner(p1, p2)
+}
+
+fn calculate_winner(left: Deck, right: Deck) -> u32 {
+    let winner: Deck = if right.is_empty() {
+        left
+    } else {
+        right
+    };
+
+    winner.iter().enumerate().map(|(i, c)| (i + 1) as u32 * c).sum()
+}
+
+fn build_deck(input: &str, deck: &str, player: &str) -> Result<Deck, ParseError> {
+    let mut deck = deck.split("\n").collect::<Vec<&str>>();
+    if deck[0] != player {
+        return Err(ParseError::at(input, deck[0], Expected::Player));
+    }
+    deck.remove(0);
+    deck.reverse();
+    deck.iter()
+        .map(|c| c.parse::<u32>().map_err(|_| ParseError::at(input, c, Expected::Card)))
+        .collect::<Result<Deck, ParseError>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_2_test() {
+        let p1 = vec![1, 3, 6, 2, 9];
+        let p2 = vec![10, 7, 4, 8, 5];
+        assert_eq!(part_2(&(p1, p2)), 291);
+    }
+}
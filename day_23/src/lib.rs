@@ -0,0 +1,132 @@
+use std::fmt;
+
+use common::Answers;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Cup,
+    UnusedCup,
+    EndOfCups,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Cup => write!(f, "a cup labelled 1 to 9"),
+            Expected::UnusedCup => write!(f, "a cup that isn't already in the circle"),
+            Expected::EndOfCups => write!(f, "the end of the nine cups"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Vec<usize>;
+
+// part 2 carries on the labels from 10, so the input has to be exactly the cups 1 to 9
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    let labels = input.trim();
+    let mut cups: Vec<usize> = Vec::new();
+    for (i, c) in labels.char_indices() {
+        let cup = match c.to_digit(10) {
+            Some(cup) if cup > 0 => cup as usize,
+            _ => return Err(ParseError::at(input, &labels[i..], Expected::Cup)),
+        };
+        if cups.len() == 9 {
+            return Err(ParseError::at(input, &labels[i..], Expected::EndOfCups));
+        }
+        if cups.contains(&cup) {
+            return Err(ParseError::at(input, &labels[i..], Expected::UnusedCup));
+        }
+        cups.push(cup);
+    }
+    if cups.len() < 9 {
+        return Err(ParseError::at(input, &labels[labels.len()..], Expected::Cup));
+    }
+    Ok(cups)
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let cups = parse(input)?;
+    Ok(Answers::new(part_1(&cups), part_2(&cups)))
+}
+
+pub fn part_1(cups: &[usize]) -> String {
+    labels_after_one(cups.to_vec(), 100)
+}
+
+pub fn part_2(cups: &[usize]) -> usize {
+    stars_product(cups.to_vec(), 10_000_000)
+}
+
+fn stars_product(mut cups: Vec<usize>, moves: usize) -> usize {
+    for i in 10..=1_000_000 {
+        cups.push(i);
+    }
+
+    let circle = game(cups, moves);
+    circle[1] * circle[circle[1]]
+}
+
+fn labels_after_one(cups: Vec<usize>, moves: usize) -> String {
+    let circle = game(cups, moves);
+    build_result(circle)
+}
+
+fn game(cups: Vec<usize>, moves: usize) -> Vec<usize> {
+    let min = *cups.iter().min().expect("couldn't get min");
+    let max = *cups.iter().max().expect("couldn't get max");
+
+    let len = cups.len();
+    let mut circle = vec![0; max + 1];
+    for i in 0..len {
+        circle[cups[i]] = cups[(i + 1) % len];
+    }
+
+    let mut first = cups[0];
+    for _i in 0..moves {
+        calculate_move(&mut circle, first, min, max);
+        first = circle[first];
+    }
+    circle
+}
+
+fn build_result(circle: Vec<usize>) -> String {
+    let mut result = String::new();
+    let mut i = 1;
+    while circle[i] != 1 {
+        result.push_str(&circle[i].to_string());
+        i = circle[i];
+    }
+    result
+}
+
+fn calculate_move(circle: &mut [usize], first: usize, min: usize, max: usize) {
+    let p1 = circle[first];
+    let p2 = circle[p1];
+    let p3 = circle[p2];
+    circle[first] = circle[p3];
+
+    let mut dest = if first > min { first - 1 } else { max };
+    while [p1, p2, p3].contains(&dest) || dest < min || dest > max  {
+        dest = if dest > min { dest - 1 } else { max };
+    }
+
+    let tmp = circle[dest];
+    circle[dest] = p1;
+    circle[p1] = p2;
+    circle[p2] = p3;
+    circle[p3] = tmp;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_test() {
+        let input = parse("389125467").unwrap();
+        assert_eq!(labels_after_one(input.clone(), 10), "92658374".to_owned());
+        assert_eq!(part_1(&input), "67384529".to_owned())
+    }
+}
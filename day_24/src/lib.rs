@@ -0,0 +1,166 @@
+use std::collections::HashSet;
+use std::fmt;
+use regex::Regex;
+
+use common::automaton::{Automaton, Hex, Rule};
+use common::Answers;
+
+pub type Tile = (i32, i32, i32);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Direction,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Direction => write!(f, "one of 'e', 'se', 'sw', 'w', 'nw' or 'ne'"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Vec<Vec<usize>>;
+
+const DIRECTIONS: [(i32, i32, i32); 6] = [
+    (1, -1, 0),
+    (-1, 1, 0),
+    (0, 1, -1),
+    (1, 0, -1),
+    (0, -1, 1),
+    (-1, 0, 1),
+];
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let instructions = parse(input)?;
+    Ok(Answers::new(part_1(&instructions), part_2(&instructions)))
+}
+
+pub fn part_1(instructions: &[Vec<usize>]) -> usize {
+    lay_tiles(instructions).len()
+}
+
+pub fn part_2(instructions: &[Vec<usize>]) -> usize {
+    live(lay_tiles(instructions), 100)
+}
+
+// black tiles with one or two black neighbours stay black, white tiles with two turn black
+fn live(floor: HashSet<Tile>, days: usize) -> usize {
+    let mut floor = Automaton::new(Hex, Rule::new(&[2], &[1, 2]), floor);
+    floor.run(days);
+    floor.population()
+}
+
+fn lay_tiles(instructions: &[Vec<usize>]) -> HashSet<Tile> {
+    let mut floor: HashSet<Tile> = HashSet::new();
+    for instruction in instructions {
+        let mut current: Tile = (0, 0, 0);
+        for &i in instruction {
+            let (mut x, mut y, mut z) = current;
+            let change = DIRECTIONS[i];
+            x += change.0;
+            y += change.1;
+            z += change.2;
+            current = (x, y, z);
+        }
+        match floor.contains(&current) {
+            true => floor.remove(&current),
+            false => floor.insert(current)
+        };
+    }
+
+    floor
+}
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    let regex = "(se)|(nw)|(ne)|(sw)|(e)|(w)";
+    let re = Regex::new(regex).expect("Failed to create regex");
+    input.trim()
+         .split("\n")
+         .map(|instruction| {
+             // the directions have to cover the whole line, anything between two matches is junk
+             let mut end = 0;
+             let directions = re.find_iter(instruction)
+                                .map(|i| {
+                                    if i.start() != end {
+                                        return Err(ParseError::at(input, &instruction[end..], Expected::Direction));
+                                    }
+                                    end = i.end();
+                                    Ok(match i.as_str() {
+                                        "e" => 0,
+                                        "w" => 1,
+                                        "nw" => 2,
+                                        "ne" => 3,
+                                        "se" => 4,
+                                        _ => 5,
+                                    })
+                                })
+                                .collect::<Result<Vec<usize>, ParseError>>()?;
+             if end != instruction.len() {
+                 return Err(ParseError::at(input, &instruction[end..], Expected::Direction));
+             }
+             Ok(directions)
+         })
+         .collect::<Result<Vec<Vec<usize>>, ParseError>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_2_test() {
+        let input = "sesenwnenenewseeswwswswwnenewsewsw\n\
+                     neeenesenwnwwswnenewnwwsewnenwseswesw\n\
+                     seswneswswsenwwnwse\n\
+                     nwnwneseeswswnenewneswwnewseswneseene\n\
+                     swweswneswnenwsewnwneneseenw\n\
+                     eesenwseswswnenwswnwnwsewwnwsene\n\
+                     sewnenenenesenwsewnenwwwse\n\
+                     wenwwweseeeweswwwnwwe\n\
+                     wsweesenenewnwwnwsenewsenwwsesesenwne\n\
+                     neeswseenwwswnwswswnw\n\
+                     nenwswwsewswnenenewsenwsenwnesesenew\n\
+                     enewnwewneswsewnwswenweswnenwsenwsw\n\
+                     sweneswneswneneenwnewenewwneswswnese\n\
+                     swwesenesewenwneswnwwneseswwne\n\
+                     enesenwswwswneneswsenwnewswseenwsese\n\
+                     wnwnesenesenenwwnenwsewesewsesesew\n\
+                     nenewswnwewswnenesenwnesewesw\n\
+                     eneswnwswnwsenenwnwnwwseeswneewsenese\n\
+                     neswnwewnwnwseenwseesewsenwsweewe\n\
+                     wseweeenwnesenwwwswnew";
+
+        let instructions = parse(input).unwrap();
+        assert_eq!(part_2(&instructions), 2208);
+    }
+
+    #[test]
+    fn part_1_test() {
+        let input = "sesenwnenenewseeswwswswwnenewsewsw\n\
+                     neeenesenwnwwswnenewnwwsewnenwseswesw\n\
+                     seswneswswsenwwnwse\n\
+                     nwnwneseeswswnenewneswwnewseswneseene\n\
+                     swweswneswnenwsewnwneneseenw\n\
+                     eesenwseswswnenwswnwnwsewwnwsene\n\
+                     sewnenenenesenwsewnenwwwse\n\
+                     wenwwweseeeweswwwnwwe\n\
+                     wsweesenenewnwwnwsenewsenwwsesesenwne\n\
+                     neeswseenwwswnwswswnw\n\
+                     nenwswwsewswnenenewsenwsenwnesesenew\n\
+                     enewnwewneswsewnwswenweswnenwsenwsw\n\
+                     sweneswneswneneenwnewenewwneswswnese\n\
+                     swwesenesewenwneswnwwneseswwne\n\
+                     enesenwswwswneneswsenwnewswseenwsese\n\
+                     wnwnesenesenenwwnenwsewesewsesesew\n\
+                     nenewswnwewswnenesenwnesewesw\n\
+                     eneswnwswnwsenenwnwnwwseeswneewsenese\n\
+                     neswnwewnwnwseenwseesewsenwsweewe\n\
+                     wseweeenwnesenwwwswnew";
+
+        let instructions = parse(input).unwrap();
+        assert_eq!(part_1(&instructions), 10);
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+
+use common::Answers;
+
+const SUB_NUM: u64 = 7;
+const DIV: u64 = 20201227;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Key,
+    EndOfInput,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Key => write!(f, "a public key below 20201227"),
+            Expected::EndOfInput => write!(f, "nothing after the two keys"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = (u64, u64);
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    let keys = input.split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|key| !key.is_empty())
+                    .collect::<Vec<&str>>();
+    if keys.len() > 2 {
+        return Err(ParseError::at(input, keys[2], Expected::EndOfInput));
+    }
+    let mut parsed = keys.iter().map(|key| match key.parse::<u64>() {
+        Ok(val) if val < DIV => Ok(val),
+        _ => Err(ParseError::at(input, key, Expected::Key)),
+    });
+    let end = &input[input.len()..];
+    let card = parsed.next().unwrap_or_else(|| Err(ParseError::at(input, end, Expected::Key)))?;
+    let door = parsed.next().unwrap_or_else(|| Err(ParseError::at(input, end, Expected::Key)))?;
+    Ok((card, door))
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let keys = parse(input)?;
+    Ok(Answers { part_1: part_1(&keys).to_string(), part_2: None })
+}
+
+pub fn part_1(&(key, door): &Parsed) -> u64 {
+    let mut lsize = 0;
+    let mut pkey = 1;
+    let mut k_loop: Option<u64> = None;
+    let mut d_loop: Option<u64> = None;
+    loop {
+        pkey = (pkey * SUB_NUM) % DIV;
+        lsize += 1;
+        if pkey == key {
+            k_loop = Some(lsize);
+            break;
+        }
+        if pkey == door {
+            d_loop = Some(lsize);
+            break;
+        }
+    }
+    if let Some(d) = d_loop {
+        return transform(key, d);
+    }
+    if let Some(k) = k_loop {
+        return transform(door, k);
+    }
+    panic!("hmmm");
+}
+
+fn transform(key: u64, lsize: u64) -> u64 {
+    let mut e_key = 1;
+    for _i in 0..lsize {
+        e_key = (e_key * key) % DIV;
+    }
+    e_key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_test() {
+        let key_1 = 5764801;
+        let key_2 = 17807724;
+
+        assert_eq!(transform(key_2, 8), 14897079);
+        assert_eq!(transform(key_1, 11), 14897079);
+        assert_eq!(part_1(&(key_1, key_2)), 14897079);
+        assert_eq!(parse("5764801\n17807724\n"), Ok((key_1, key_2)));
+        assert_eq!(parse("5764801,17807724"), Ok((key_1, key_2)));
+    }
+}
@@ -0,0 +1,218 @@
+use std::fmt;
+
+use common::{Answers, Grid};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slope {
+    pub right: usize,
+    pub down: usize,
+}
+
+impl fmt::Display for Slope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "right {}, down {}", self.right, self.down)
+    }
+}
+
+struct Position {
+    x: usize,
+    y: usize,
+}
+
+// the forest repeats to the right forever
+fn is_tree(map: &Grid<char>, pos: &Position) -> u32 {
+    if *map.get_wrapping(pos.x as isize, pos.y as isize) == '#' {
+        return 1
+    }
+    0
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Square,
+    EndOfRow,
+    Slope,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Square => write!(f, "'.' or '#'"),
+            Expected::EndOfRow => write!(f, "the end of the row"),
+            Expected::Slope => write!(f, "'<right>,<down>' with down at least 1"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Grid<char>;
+
+// every row has to be as wide as the first one for the map to repeat
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    let square = |c| match c {
+        '.' | '#' => Some(c),
+        _ => None,
+    };
+    Grid::parse(input, square, Expected::Square, Expected::EndOfRow)
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let map = parse(input)?;
+    Ok(Answers::new(part_1(&map), part_2(&map)))
+}
+
+/// Reads slopes written as `<right>,<down>`, separated by whitespace, e.g. `3,1 1,2`.
+pub fn parse_slopes(input: &str) -> Result<Vec<Slope>, ParseError> {
+    input.split_whitespace()
+         .map(|slope| {
+             let parsed = slope.split_once(',')
+                               .and_then(|(right, down)| Some((right.parse::<usize>().ok()?, down.parse::<usize>().ok()?)));
+             match parsed {
+                 // going nowhere would never reach the bottom
+                 Some((right, down)) if down > 0 => Ok(Slope { right, down }),
+                 _ => Err(ParseError::at(input, slope, Expected::Slope)),
+             }
+         })
+         .collect()
+}
+
+pub fn build_slopes() -> Vec<Slope> {
+    vec![
+        Slope{right: 1, down: 1},
+        Slope{right: 3, down: 1},
+        Slope{right: 5, down: 1},
+        Slope{right: 7, down: 1},
+        Slope{right: 1, down: 2},
+    ]
+}
+
+pub fn part_1(map: &Grid<char>) -> u32 {
+    count_trees(map, Slope{right: 3, down: 1})
+}
+
+pub fn part_2(map: &Grid<char>) -> u32 {
+    let mut count: u32 = 1;
+    for slope in build_slopes() {
+        count *= count_trees(map, slope);
+    }
+    count
+}
+
+pub fn count_trees(map: &Grid<char>, slope: Slope) -> u32 {
+    path(map, slope).iter().map(|pos| is_tree(map, pos)).sum()
+}
+
+// every position from the top left to the bottom, x keeps going past the edge
+fn path(map: &Grid<char>, slope: Slope) -> Vec<Position> {
+    (0..map.height()).step_by(slope.down)
+                     .enumerate()
+                     .map(|(step, y)| Position { x: step * slope.right, y })
+                     .collect()
+}
+
+/// The slopes with the fewest and the most trees of every slope going at most
+/// `bounds.right` right and `bounds.down` down. Ties go to the slope found first,
+/// going right before down.
+pub fn search(map: &Grid<char>, bounds: Slope) -> ((Slope, u32), (Slope, u32)) {
+    let mut fewest: Option<(Slope, u32)> = None;
+    let mut most: Option<(Slope, u32)> = None;
+    for down in 1..=bounds.down {
+        for right in 0..=bounds.right {
+            let slope = Slope { right, down };
+            let trees = count_trees(map, slope);
+            if fewest.is_none_or(|(_, n)| trees < n) {
+                fewest = Some((slope, trees));
+            }
+            if most.is_none_or(|(_, n)| trees > n) {
+                most = Some((slope, trees));
+            }
+        }
+    }
+    (fewest.unwrap(), most.unwrap())
+}
+
+/// The map with the squares `slope` passes through marked, `O` for open and
+/// `X` for a tree. The forest repeats, so the path is folded back onto a single
+/// copy of it; every row is visited at most once so no marks overlap.
+pub fn render(map: &Grid<char>, slope: Slope) -> String {
+    let mut rendered = map.clone();
+    for pos in path(map, slope) {
+        let x = pos.x % map.width();
+        rendered[(x, pos.y)] = match is_tree(map, &pos) {
+            1 => 'X',
+            _ => 'O',
+        };
+    }
+    rendered.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_tree_tests() {
+        let map = parse("...\n###").unwrap();
+        let pos = Position { x: 0, y: 0 };
+        assert_eq!(is_tree(&map, &pos), 0);
+
+        let pos = Position { x: 1, y: 1 };
+        assert_eq!(is_tree(&map, &pos), 1);
+    }
+
+    #[test]
+    fn wrapping_tests() {
+        let map = parse("..#\n#..").unwrap();
+        assert_eq!(is_tree(&map, &Position { x: 5, y: 0 }), 1);
+        assert_eq!(is_tree(&map, &Position { x: 6, y: 1 }), 1);
+        assert_eq!(is_tree(&map, &Position { x: 7, y: 1 }), 0);
+    }
+
+    #[test]
+    fn part_1_tests() {
+        let map = parse("...\n###").unwrap();
+
+        assert_eq!(count_trees(&map, Slope{right: 3, down: 1}), 1);
+    }
+
+    #[test]
+    fn slope_tests() {
+        let map = parse("..##.......\n\
+                         #...#...#..\n\
+                         .#....#..#.\n\
+                         ..#.#...#.#\n\
+                         .#...##..#.\n\
+                         ..#.##.....\n\
+                         .#.#.#....#\n\
+                         .#........#\n\
+                         #.##...#...\n\
+                         #...##....#\n\
+                         .#..#...#.#").unwrap();
+
+        let slopes = parse_slopes("1,1 3,1\n5,1 7,1 1,2").unwrap();
+        assert_eq!(slopes, build_slopes());
+        assert_eq!(slopes.iter().map(|slope| count_trees(&map, *slope)).collect::<Vec<u32>>(), vec![2, 7, 3, 4, 2]);
+        assert_eq!(part_2(&map), 336);
+
+        let err = parse_slopes("3,1 2,0").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (1, 5, Expected::Slope));
+        assert!(parse_slopes("3").is_err());
+
+        let (fewest, most) = search(&map, Slope { right: 7, down: 2 });
+        assert_eq!(fewest, (Slope { right: 5, down: 2 }, 0));
+        assert_eq!(most, (Slope { right: 3, down: 1 }, 7));
+
+        assert_eq!(render(&map, Slope { right: 3, down: 1 }), "O.##.......\n\
+                                                              #..O#...#..\n\
+                                                              .#....X..#.\n\
+                                                              ..#.#...#O#\n\
+                                                              .X...##..#.\n\
+                                                              ..#.X#.....\n\
+                                                              .#.#.#.O..#\n\
+                                                              .#........X\n\
+                                                              #.X#...#...\n\
+                                                              #...#X....#\n\
+                                                              .#..#...X.#");
+    }
+}
@@ -1,109 +1,52 @@
-use std::io::Read;
-use std::str;
+use std::process;
 
-struct Slope {
-    right: usize,
-    down: usize,
-}
-
-struct Position {
-    x: usize,
-    y: usize,
-}
-
-fn read_file(filename: &str) -> String {
-    let mut file = std::fs::File::open(filename).expect("Failed to open file");
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).expect("Failed to read file");
-
-    contents
-}
-
-fn is_tree(map: &Vec<Vec<char>>, pos: &Position) -> u32 {
-    if map[pos.y][pos.x] == '#' {
-        return 1
-    }
-    return 0;
-}
-
-fn extend_map(map: &Vec<Vec<char>>) -> Vec<Vec<char>> {
-    let mut new_map = Vec::new();
-    for y in map {
-        let mut new_y = y.clone();
-        new_y.append(&mut y.clone());
-        new_map.push(new_y);
-    }
-    new_map
-}
-
-fn part_1(mut map: Vec<Vec<char>>, slope: Slope) -> u32 {
-    let mut pos = Position { x: 0, y: 0 };
-    let mut count: u32 = is_tree(&map, &pos);
-    let last = map.len();
-    while pos.y < last {
-        if pos.x >= map[0].len() {
-            map = extend_map(&map);
-        }
-        count += is_tree(&map, &pos);
-        pos.y += slope.down;
-        pos.x += slope.right;
-    }
-    count
-}
-
-fn part_2(map: Vec<Vec<char>>, slopes: Vec<Slope>) -> u32 {
-    let mut count: u32 = 1;
-    for slope in slopes {
-        count *= part_1(map.clone(), slope);
-    }
-    count
-}
-
-fn is_tree_tests() {
-    let mut map: Vec<Vec<char>> = Vec::new();
-    map.push(['.','.','.'].to_vec());
-    map.push(['#','#','#'].to_vec());
-    let pos = Position { x: 0, y: 0 };
-    assert_eq!(is_tree(&map, &pos), 0);
-
-    let pos = Position { x: 1, y: 1 };
-    assert_eq!(is_tree(&map, &pos), 1);
-}
+use common::{day_args, or_exit, read_or_exit, Input};
+use day_3::{count_trees, parse, parse_slopes, part_1, part_2, render, search};
 
-fn extend_map_tests() {
-    let mut map: Vec<Vec<char>> = Vec::new();
-    map.push(['.','.','.'].to_vec());
-    map.push(['#','#','#'].to_vec());
-    extend_map(&map);
-    assert_eq!(map[0].len(), 6);
-    assert_eq!(map[1].len(), 6);
-}
-
-fn part_1_tests() {
-    let mut map: Vec<Vec<char>> = Vec::new();
-    map.push(['.','.','.'].to_vec());
-    map.push(['#','#','#'].to_vec());
-
-    assert_eq!(part_1(map, Slope{right: 3, down: 1}), 1);
-}
+const USAGE: &str = "usage: day_3 [input] [--slopes <path|value>] [--search <right>,<down>]";
 
 fn main() {
-    let contents = read_file("input.txt");
-
-    let mut map: Vec<Vec<char>> = Vec::new();
-    for line in contents.lines() {
-        map.push(line.chars().collect());
+    let (input, options) = day_args();
+    let map = or_exit(parse(&read_or_exit(&input)));
+
+    match options.iter().map(|option| option.as_str()).collect::<Vec<&str>>()[..] {
+        [] => {
+            let part_1_result = part_1(&map);
+            let part_2_result = part_2(&map);
+            println!("Part 1: {}", part_1_result);
+            println!("Part 2: {}", part_2_result);
+        },
+        ["--slopes", slopes] => {
+            // slopes written out in full, or else the file they're in
+            let slopes = match parse_slopes(slopes) {
+                Ok(slopes) => slopes,
+                Err(_) => or_exit(parse_slopes(&read_or_exit(&Input::from_arg(slopes)))),
+            };
+            let mut product: u64 = 1;
+            for slope in slopes {
+                let trees = count_trees(&map, slope);
+                product *= trees as u64;
+                println!("{}: {} trees", slope, trees);
+            }
+            println!("product: {}", product);
+        },
+        ["--search", bounds] => {
+            let bounds = match or_exit(parse_slopes(bounds))[..] {
+                [bounds] => bounds,
+                _ => {
+                    eprintln!("--search needs a single '<right>,<down>'");
+                    process::exit(2);
+                }
+            };
+            let (fewest, most) = search(&map, bounds);
+            println!("fewest trees: {} hits {}", fewest.0, fewest.1);
+            println!("{}\n", render(&map, fewest.0));
+            println!("most trees: {} hits {}", most.0, most.1);
+            println!("{}", render(&map, most.0));
+        },
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        },
     }
-
-    let mut slopes: Vec<Slope> = Vec::new();
-    slopes.push(Slope{right: 1, down: 1});
-    slopes.push(Slope{right: 3, down: 1});
-    slopes.push(Slope{right: 5, down: 1});
-    slopes.push(Slope{right: 7, down: 1});
-    slopes.push(Slope{right: 1, down: 2});
-
-    let part_1_result = part_1(map.clone(), Slope{right: 3, down: 1});
-    let part_2_result = part_2(map.clone(), slopes);
-    println!("Part 1: {}", part_1_result);
-    println!("Part 2: {}", part_2_result);
 }
@@ -0,0 +1,378 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use common::Answers;
+
+pub struct Property {
+    key: String,
+    value: String
+}
+
+/// The rules passports are held to, see `passport.schema` for the format.
+pub const PASSPORT_SCHEMA: &str = include_str!("../passport.schema");
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Key,
+    Colon,
+    Requirement,
+    Rule,
+    Range,
+    Unit,
+    Pattern,
+    Value,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Key => write!(f, "a field name"),
+            Expected::Colon => write!(f, "'key:value'"),
+            Expected::Requirement => write!(f, "'required' or 'optional'"),
+            Expected::Rule => write!(f, "'int', 'unit', 'pattern' or 'one-of'"),
+            Expected::Range => write!(f, "a range like '1920..=2002'"),
+            Expected::Unit => write!(f, "a unit followed by its range"),
+            Expected::Pattern => write!(f, "a regular expression"),
+            Expected::Value => write!(f, "at least one value"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Vec<Vec<Property>>;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    input.split("\n\n")
+         .map(|passport| parse_properties(input, passport))
+         .collect::<Result<Vec<Vec<Property>>, ParseError>>()
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let passports = parse(input)?;
+    Ok(Answers::new(part_1(&passports), part_2(&passports)))
+}
+
+fn parse_properties(input: &str, passport: &str) -> Result<Vec<Property>, ParseError> {
+    let mut parsed: Vec<Property> = Vec::new();
+    for property in passport.split_whitespace() {
+        let (key, value) = property.split_once(":")
+                                   .ok_or_else(|| ParseError::at(input, property, Expected::Colon))?;
+        if key.is_empty() {
+            return Err(ParseError::at(input, property, Expected::Key));
+        }
+        parsed.push(Property {
+            key: key.to_string(),
+            value: value.to_string()
+        });
+    }
+
+    Ok(parsed)
+}
+
+#[derive(Debug)]
+pub enum Rule {
+    Present,
+    Int(RangeInclusive<i64>),
+    Unit(Vec<(String, RangeInclusive<i64>)>),
+    Pattern(Regex),
+    OneOf(Vec<String>),
+}
+
+#[derive(Debug)]
+pub struct Field {
+    pub name: String,
+    pub required: bool,
+    pub rule: Rule,
+}
+
+/// The fields a document can have and what their values have to look like.
+#[derive(Debug)]
+pub struct Schema {
+    pub fields: Vec<Field>,
+}
+
+/// Why a field failed validation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reason {
+    Missing,
+    NotANumber,
+    OutOfRange(RangeInclusive<i64>),
+    UnknownUnit(Vec<String>),
+    NoMatch(String),
+    NotOneOf(Vec<String>),
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reason::Missing => write!(f, "missing"),
+            Reason::NotANumber => write!(f, "not a whole number"),
+            Reason::OutOfRange(range) => write!(f, "outside {}..={}", range.start(), range.end()),
+            Reason::UnknownUnit(units) => write!(f, "needs one of the units {}", units.join(", ")),
+            Reason::NoMatch(pattern) => write!(f, "doesn't match {}", pattern),
+            Reason::NotOneOf(values) => write!(f, "not one of {}", values.join(", ")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    pub field: String,
+    pub value: Option<String>,
+    pub reason: Reason,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{} '{}': {}", self.field, value, self.reason),
+            None => write!(f, "{}: {}", self.field, self.reason),
+        }
+    }
+}
+
+/// Every field of one document that broke the schema, in schema order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validation {
+    pub failures: Vec<Failure>,
+}
+
+impl Validation {
+    pub fn valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl fmt::Display for Validation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for failure in &self.failures {
+            writeln!(f, "{}", failure)?;
+        }
+        Ok(())
+    }
+}
+
+impl Schema {
+    /// Reads one `<field> <required|optional> [rule]` per line, skipping blank
+    /// lines and `#` comments.
+    pub fn parse(input: &str) -> Result<Schema, ParseError> {
+        let mut fields = Vec::new();
+        for line in input.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let end = &line[line.len()..];
+            let mut tokens = line.split_whitespace();
+            let name = tokens.next().unwrap();
+            let required = match tokens.next() {
+                Some("required") => true,
+                Some("optional") => false,
+                token => return Err(ParseError::at(input, token.unwrap_or(end), Expected::Requirement)),
+            };
+            let rule = match tokens.next() {
+                None => Rule::Present,
+                Some("int") => Rule::Int(read_range(input, tokens.next().unwrap_or(end))?),
+                Some("unit") => {
+                    let tokens = tokens.by_ref().collect::<Vec<&str>>();
+                    if tokens.is_empty() || tokens.len() % 2 != 0 {
+                        return Err(ParseError::at(input, tokens.last().unwrap_or(&end), Expected::Unit));
+                    }
+                    let units = tokens.chunks(2)
+                                      .map(|unit| Ok((unit[0].to_owned(), read_range(input, unit[1])?)))
+                                      .collect::<Result<Vec<(String, RangeInclusive<i64>)>, ParseError>>()?;
+                    Rule::Unit(units)
+                },
+                Some("pattern") => {
+                    let pattern = tokens.next().unwrap_or(end);
+                    // the whole value has to match, not just part of it
+                    let regex = Regex::new(&format!("^(?:{})$", pattern))
+                                      .map_err(|_| ParseError::at(input, pattern, Expected::Pattern))?;
+                    Rule::Pattern(regex)
+                },
+                Some("one-of") => {
+                    let values = tokens.by_ref().map(|value| value.to_owned()).collect::<Vec<String>>();
+                    if values.is_empty() {
+                        return Err(ParseError::at(input, end, Expected::Value));
+                    }
+                    Rule::OneOf(values)
+                },
+                Some(token) => return Err(ParseError::at(input, token, Expected::Rule)),
+            };
+            if let Some(token) = tokens.next() {
+                return Err(ParseError::at(input, token, Expected::Rule));
+            }
+            fields.push(Field { name: name.to_owned(), required, rule });
+        }
+        Ok(Schema { fields })
+    }
+
+    /// The bundled passport schema, parsed the first time it's asked for.
+    pub fn passport() -> &'static Schema {
+        static PASSPORT: OnceLock<Schema> = OnceLock::new();
+        PASSPORT.get_or_init(|| Schema::parse(PASSPORT_SCHEMA).expect("The bundled passport schema is broken"))
+    }
+
+    /// Checks only that every required field is there.
+    pub fn check_present(&self, document: &[Property]) -> Validation {
+        let failures = self.fields.iter()
+                                  .filter(|field| field.required && find(document, &field.name).is_none())
+                                  .map(|field| Failure { field: field.name.clone(), value: None, reason: Reason::Missing })
+                                  .collect();
+        Validation { failures }
+    }
+
+    /// Checks every field in the schema against its rule. Fields the schema
+    /// doesn't mention are left alone.
+    pub fn validate(&self, document: &[Property]) -> Validation {
+        let mut failures = Vec::new();
+        for field in &self.fields {
+            let value = match find(document, &field.name) {
+                Some(value) => value,
+                None if field.required => {
+                    failures.push(Failure { field: field.name.clone(), value: None, reason: Reason::Missing });
+                    continue;
+                },
+                None => continue,
+            };
+            if let Err(reason) = field.rule.check(value) {
+                failures.push(Failure { field: field.name.clone(), value: Some(value.to_owned()), reason });
+            }
+        }
+        Validation { failures }
+    }
+}
+
+impl Rule {
+    pub fn check(&self, value: &str) -> Result<(), Reason> {
+        match self {
+            Rule::Present => Ok(()),
+            Rule::Int(range) => check_int(value, range),
+            Rule::Unit(units) => {
+                match units.iter().find(|(unit, _)| value.ends_with(unit.as_str())) {
+                    Some((unit, range)) => check_int(&value[..value.len() - unit.len()], range),
+                    None => Err(Reason::UnknownUnit(units.iter().map(|(unit, _)| unit.clone()).collect())),
+                }
+            },
+            Rule::Pattern(regex) => match regex.is_match(value) {
+                true => Ok(()),
+                // show the pattern as it was written, without the anchors
+                false => Err(Reason::NoMatch(regex.as_str()[4..regex.as_str().len() - 2].to_owned())),
+            },
+            Rule::OneOf(values) => match values.iter().any(|allowed| allowed == value) {
+                true => Ok(()),
+                false => Err(Reason::NotOneOf(values.clone())),
+            },
+        }
+    }
+}
+
+fn check_int(value: &str, range: &RangeInclusive<i64>) -> Result<(), Reason> {
+    let number = value.parse::<i64>().map_err(|_| Reason::NotANumber)?;
+    match range.contains(&number) {
+        true => Ok(()),
+        false => Err(Reason::OutOfRange(range.clone())),
+    }
+}
+
+fn read_range(input: &str, token: &str) -> Result<RangeInclusive<i64>, ParseError> {
+    let (min, max) = token.split_once("..=")
+                          .and_then(|(min, max)| Some((min.parse::<i64>().ok()?, max.parse::<i64>().ok()?)))
+                          .ok_or_else(|| ParseError::at(input, token, Expected::Range))?;
+    Ok(min..=max)
+}
+
+fn find<'a>(document: &'a [Property], key: &str) -> Option<&'a str> {
+    document.iter()
+            .find(|prop| prop.key == key)
+            .map(|prop| prop.value.as_str())
+}
+
+pub fn part_1(passports: &[Vec<Property>]) -> usize {
+    count_complete(Schema::passport(), passports)
+}
+
+pub fn part_2(passports: &[Vec<Property>]) -> usize {
+    count_valid(Schema::passport(), passports)
+}
+
+/// How many documents have every field `schema` requires.
+pub fn count_complete(schema: &Schema, documents: &[Vec<Property>]) -> usize {
+    documents.iter()
+             .filter(|properties| schema.check_present(properties).valid())
+             .count()
+}
+
+/// How many documents pass every rule in `schema`.
+pub fn count_valid(schema: &Schema, documents: &[Vec<Property>]) -> usize {
+    documents.iter()
+             .filter(|properties| schema.validate(properties).valid())
+             .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_tests() {
+        let schema = Schema::passport();
+        assert_eq!(schema.fields.iter().map(|field| field.name.as_str()).collect::<Vec<&str>>(),
+                   vec!["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"]);
+        assert!(!schema.fields[7].required);
+        assert!(std::ptr::eq(schema, Schema::passport()));
+
+        let err = Schema::parse("a required\nb sometimes").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (2, 3, Expected::Requirement));
+        let err = Schema::parse("a required int 10..20").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (1, 16, Expected::Range));
+        let err = Schema::parse("a required unit cm 1..=2 in").err().unwrap();
+        assert_eq!((err.expected, err.found.as_str()), (Expected::Unit, "in"));
+        let err = Schema::parse("a required pattern [0-9").err().unwrap();
+        assert_eq!((err.expected, err.found.as_str()), (Expected::Pattern, "[0-9"));
+        let err = Schema::parse("a required one-of").err().unwrap();
+        assert_eq!(err.expected, Expected::Value);
+        let err = Schema::parse("a required int 1..=2 3..=4").err().unwrap();
+        assert_eq!((err.expected, err.found.as_str()), (Expected::Rule, "3..=4"));
+        assert_eq!(Schema::parse("a required float").err().unwrap().expected, Expected::Rule);
+
+        let schema = Schema::parse("# an invoice\nid required pattern INV-[0-9]+\n\ntotal required unit EUR 0..=1000\nnote optional").unwrap();
+        let invoice = parse("id:INV-17 total:80EUR").unwrap();
+        assert!(schema.validate(&invoice[0]).valid());
+        assert_eq!((count_complete(&schema, &invoice), count_valid(&schema, &invoice)), (1, 1));
+        let invoice = parse("id:17 note:late").unwrap();
+        assert_eq!(schema.validate(&invoice[0]).to_string(),
+                   "id '17': doesn't match INV-[0-9]+\ntotal: missing\n");
+    }
+
+    #[test]
+    fn validation_tests() {
+        let schema = Schema::passport();
+
+        let valid = parse("pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980\nhcl:#623a2f").unwrap();
+        assert!(schema.validate(&valid[0]).valid());
+
+        let invalid = parse("eyr:1972 cid:100\nhcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926").unwrap();
+        assert!(schema.check_present(&invalid[0]).valid());
+        assert_eq!(schema.validate(&invalid[0]).failures, vec![
+            Failure { field: "eyr".to_owned(), value: Some("1972".to_owned()), reason: Reason::OutOfRange(2020..=2030) },
+            Failure { field: "hgt".to_owned(), value: Some("170".to_owned()), reason: Reason::UnknownUnit(vec!["cm".to_owned(), "in".to_owned()]) },
+            Failure { field: "pid".to_owned(), value: Some("186cm".to_owned()), reason: Reason::NoMatch("[0-9]{9}".to_owned()) },
+        ]);
+
+        let invalid = parse("hcl:dab227 iyr:2012\necl:zzz eyr:2020 hgt:200cm byr:19x0").unwrap();
+        assert_eq!(schema.validate(&invalid[0]).to_string(), "\
+byr '19x0': not a whole number
+hgt '200cm': outside 150..=193
+hcl 'dab227': doesn't match #[0-9a-f]{6}
+ecl 'zzz': not one of amb, blu, brn, gry, grn, hzl, oth
+pid: missing
+");
+        assert_eq!(schema.check_present(&invalid[0]).failures,
+                   vec![Failure { field: "pid".to_owned(), value: None, reason: Reason::Missing }]);
+    }
+}
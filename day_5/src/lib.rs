@@ -0,0 +1,260 @@
+use std::fmt;
+
+use common::{Answers, Grid};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ticket {
+    pub id: u32,
+    pub row: u32,
+    pub col: u32,
+}
+
+/// How many rows and columns of seats an aircraft has. Boarding passes pick a
+/// seat by halving the rows and then the columns, so both have to be powers
+/// of two.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Layout {
+    rows: u32,
+    cols: u32,
+}
+
+impl Layout {
+    pub const AIRCRAFT: Layout = Layout { rows: 128, cols: 8 };
+
+    pub fn new(rows: u32, cols: u32) -> Option<Layout> {
+        match rows.is_power_of_two() && cols.is_power_of_two() {
+            true => Some(Layout { rows, cols }),
+            false => None,
+        }
+    }
+
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    pub fn cols(&self) -> u32 {
+        self.cols
+    }
+
+    // how many letters of a boarding pass pick the row and how many the column
+    fn row_letters(&self) -> usize {
+        self.rows.trailing_zeros() as usize
+    }
+
+    fn col_letters(&self) -> usize {
+        self.cols.trailing_zeros() as usize
+    }
+
+    pub fn ticket(&self, row: u32, col: u32) -> Ticket {
+        Ticket { row, col, id: row * self.cols + col }
+    }
+
+    pub fn decode(&self, pass: &str) -> Result<Ticket, ParseError> {
+        build_ticket(pass, pass, *self)
+    }
+
+    pub fn encode(&self, ticket: &Ticket) -> String {
+        let row = (0..self.row_letters()).rev()
+                                         .map(|bit| if ticket.row >> bit & 1 == 1 { 'B' } else { 'F' });
+        let col = (0..self.col_letters()).rev()
+                                         .map(|bit| if ticket.col >> bit & 1 == 1 { 'R' } else { 'L' });
+        row.chain(col).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Row,
+    Column,
+    EndOfPass,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Row => write!(f, "'F' or 'B'"),
+            Expected::Column => write!(f, "'L' or 'R'"),
+            Expected::EndOfPass => write!(f, "the end of the boarding pass"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Vec<Ticket>;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    parse_with(input, Layout::AIRCRAFT)
+}
+
+pub fn parse_with(input: &str, layout: Layout) -> Result<Parsed, ParseError> {
+    input.trim()
+         .split("\n")
+         .map(|ticket| build_ticket(input, ticket.trim(), layout))
+         .collect::<Result<Vec<Ticket>, ParseError>>()
+}
+
+pub fn solve(input: &str) -> Result<Answers, String> {
+    let tickets = parse(input).map_err(|err| err.to_string())?;
+    Ok(Answers::new(part_1(&tickets), part_2(&tickets)?))
+}
+
+pub fn part_1(tickets: &[Ticket]) -> u32 {
+    let mut max: u32 = 0;
+    for ticket in tickets {
+        if max < ticket.id {
+            max = ticket.id;
+        }
+    }
+    max
+}
+
+pub fn part_2(tickets: &[Ticket]) -> Result<u32, String> {
+    my_seat(&SeatMap::new(Layout::AIRCRAFT, tickets))
+        .map(|ticket| ticket.id)
+        .ok_or_else(|| "no free seat between two taken ones".to_owned())
+}
+
+/// Which seats of an aircraft are taken.
+pub struct SeatMap {
+    layout: Layout,
+    taken: Grid<bool>,
+}
+
+impl SeatMap {
+    pub fn new(layout: Layout, tickets: &[Ticket]) -> SeatMap {
+        let mut taken = Grid::filled(layout.cols as usize, layout.rows as usize, false);
+        for ticket in tickets {
+            taken[(ticket.col as usize, ticket.row as usize)] = true;
+        }
+        SeatMap { layout, taken }
+    }
+
+    pub fn is_taken(&self, row: u32, col: u32) -> bool {
+        self.taken[(col as usize, row as usize)]
+    }
+
+    /// Every free seat with the seats either side of it in the same row taken.
+    pub fn free_seats(&self) -> Vec<Ticket> {
+        self.taken.cells()
+                  .filter(|&((x, y), taken)| {
+                      !taken && x > 0 && self.taken.get(x + 1, y) == Some(&true) && self.taken[(x - 1, y)]
+                  })
+                  .map(|((x, y), _)| self.layout.ticket(y as u32, x as u32))
+                  .collect()
+    }
+}
+
+/// Rows of `#` for taken seats and `.` for free ones, front row first.
+impl fmt::Display for SeatMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.taken.map(|&taken| if taken { '#' } else { '.' }))
+    }
+}
+
+/// The seat that's free with both its neighbours taken, as long as there's
+/// only one.
+pub fn my_seat(seats: &SeatMap) -> Option<Ticket> {
+    match seats.free_seats()[..] {
+        [ticket] => Some(ticket),
+        _ => None,
+    }
+}
+
+fn build_ticket(input: &str, ticket: &str, layout: Layout) -> Result<Ticket, ParseError> {
+    let rows = layout.row_letters();
+    let cols = rows + layout.col_letters();
+    let mut row = 0;
+    let mut col = 0;
+    let mut letters = 0;
+    for (i, letter) in ticket.char_indices() {
+        match (letters, letter) {
+            (n, 'B') if n < rows => row = row << 1 | 1,
+            (n, 'F') if n < rows => row <<= 1,
+            (n, _) if n < rows => return Err(ParseError::at(input, &ticket[i..], Expected::Row)),
+            (n, 'R') if n < cols => col = col << 1 | 1,
+            (n, 'L') if n < cols => col <<= 1,
+            (n, _) if n < cols => return Err(ParseError::at(input, &ticket[i..], Expected::Column)),
+            _ => return Err(ParseError::at(input, &ticket[i..], Expected::EndOfPass)),
+        }
+        letters += 1;
+    }
+    match letters {
+        n if n < rows => return Err(ParseError::at(input, &ticket[ticket.len()..], Expected::Row)),
+        n if n < cols => return Err(ParseError::at(input, &ticket[ticket.len()..], Expected::Column)),
+        _ => {},
+    }
+    Ok(layout.ticket(row, col))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_tests() {
+        let string1 = "FBFBBFFRLR";
+        let string2 = "BFFFBBFRRR";
+        let string3 = "FFFBBBFRRR";
+        let string4 = "BBFFBBFRLL";
+
+        assert_eq!(part_1(&parse(string1).unwrap()), 357);
+        assert_eq!(part_1(&parse(string2).unwrap()), 567);
+        assert_eq!(part_1(&parse(string3).unwrap()), 119);
+        assert_eq!(part_1(&parse(string4).unwrap()), 820);
+    }
+
+    #[test]
+    fn parse_error_tests() {
+        let err = parse("FBFBBFFRLR\nFBFBBFFRXR").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (2, 9, Expected::Column));
+
+        let err = parse("FBFBBFF").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (1, 8, Expected::Column));
+
+        let err = parse("FBFBBFFRLRL").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (1, 11, Expected::EndOfPass));
+    }
+
+    #[test]
+    fn codec_tests() {
+        let layout = Layout::AIRCRAFT;
+        let ticket = layout.decode("FBFBBFFRLR").unwrap();
+        assert_eq!(ticket, Ticket { row: 44, col: 5, id: 357 });
+        assert_eq!(layout.encode(&ticket), "FBFBBFFRLR");
+        for id in 0..layout.rows() * layout.cols() {
+            let ticket = layout.ticket(id / layout.cols(), id % layout.cols());
+            assert_eq!(ticket.id, id);
+            assert_eq!(layout.decode(&layout.encode(&ticket)), Ok(ticket));
+        }
+
+        assert_eq!(Layout::new(12, 8), None);
+        let small = Layout::new(4, 2).unwrap();
+        assert_eq!(small.encode(&small.ticket(2, 1)), "BFR");
+        assert_eq!(small.decode("FBL").unwrap(), Ticket { row: 1, col: 0, id: 2 });
+        let err = small.decode("FBLR").err().unwrap();
+        assert_eq!((err.column, err.expected), (4, Expected::EndOfPass));
+        let err = small.decode("FRL").err().unwrap();
+        assert_eq!((err.column, err.expected), (2, Expected::Row));
+        assert_eq!(parse_with("BBL\nFFR", small).unwrap(), vec![small.ticket(3, 0), small.ticket(0, 1)]);
+    }
+
+    #[test]
+    fn seat_map_tests() {
+        let layout = Layout::new(4, 4).unwrap();
+        let tickets = parse_with("FFLL\nFFRL\nFFRR\nFBLL\nFBLR\nFBRR\nBFLL\nBFRL\nBBLR\nBBRL", layout).unwrap();
+        let seats = SeatMap::new(layout, &tickets);
+        assert_eq!(seats.to_string(), "#.##\n##.#\n#.#.\n.##.");
+        assert!(seats.is_taken(3, 2));
+        assert!(!seats.is_taken(3, 3));
+
+        assert_eq!(seats.free_seats(), vec![layout.ticket(0, 1), layout.ticket(1, 2), layout.ticket(2, 1)]);
+        assert_eq!(my_seat(&seats), None);
+
+        let tickets = parse_with("FFLL\nFFRL\nFBLL", layout).unwrap();
+        let seats = SeatMap::new(layout, &tickets);
+        assert_eq!(seats.free_seats(), vec![layout.ticket(0, 1)]);
+        assert_eq!(my_seat(&seats), Some(Ticket { row: 0, col: 1, id: 1 }));
+        assert_eq!(part_2(&[]), Err("no free seat between two taken ones".to_owned()));
+    }
+}
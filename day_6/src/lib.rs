@@ -0,0 +1,435 @@
+use std::fmt;
+use std::ops::{BitAnd, BitOr, Not, Sub};
+
+use common::Answers;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Question,
+    Term,
+    Number,
+    CloseBracket,
+    CloseParen,
+    EndOfQuery,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Question => write!(f, "a question from 'a' to 'z'"),
+            Expected::Term => write!(f, "'any', 'all', 'none', 'at-least <n>', 'exactly <n>', '[<questions>]', '!' or '('"),
+            Expected::Number => write!(f, "a number of people"),
+            Expected::CloseBracket => write!(f, "']'"),
+            Expected::CloseParen => write!(f, "')'"),
+            Expected::EndOfQuery => write!(f, "'&', '|', '-' or the end of the query"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+/// A set of the questions 'a' to 'z', one bit each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Questions(u32);
+
+impl Questions {
+    pub const NONE: Questions = Questions(0);
+    pub const ALL: Questions = Questions((1 << 26) - 1);
+
+    /// Every question in `answers`, which has to hold only 'a' to 'z'.
+    pub fn from_answers(answers: &str) -> Questions {
+        Questions(answers.bytes().fold(0, |set, question| set | 1 << (question - b'a')))
+    }
+
+    pub fn contains(&self, question: char) -> bool {
+        question.is_ascii_lowercase() && self.0 >> (question as u8 - b'a') & 1 == 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = char> + '_ {
+        ('a'..='z').filter(move |&question| self.contains(question))
+    }
+}
+
+impl BitOr for Questions {
+    type Output = Questions;
+
+    fn bitor(self, other: Questions) -> Questions {
+        Questions(self.0 | other.0)
+    }
+}
+
+impl BitAnd for Questions {
+    type Output = Questions;
+
+    fn bitand(self, other: Questions) -> Questions {
+        Questions(self.0 & other.0)
+    }
+}
+
+impl Sub for Questions {
+    type Output = Questions;
+
+    fn sub(self, other: Questions) -> Questions {
+        Questions(self.0 & !other.0)
+    }
+}
+
+impl Not for Questions {
+    type Output = Questions;
+
+    fn not(self) -> Questions {
+        Questions(!self.0 & Questions::ALL.0)
+    }
+}
+
+impl fmt::Display for Questions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.iter().collect::<String>())
+    }
+}
+
+/// The questions each person in a group answered "yes" to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    pub people: Vec<Questions>,
+}
+
+impl Group {
+    /// Questions anyone in the group answered.
+    pub fn anyone(&self) -> Questions {
+        self.people.iter().fold(Questions::NONE, |set, &person| set | person)
+    }
+
+    /// Questions everyone in the group answered.
+    pub fn everyone(&self) -> Questions {
+        self.people.iter().fold(Questions::ALL, |set, &person| set & person)
+    }
+
+    /// Questions nobody in the group answered.
+    pub fn nobody(&self) -> Questions {
+        !self.anyone()
+    }
+
+    /// How many people answered each question, 'a' first.
+    pub fn counts(&self) -> [usize; 26] {
+        let mut counts = [0; 26];
+        for person in &self.people {
+            for question in person.iter() {
+                counts[(question as u8 - b'a') as usize] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Questions answered by `k` or more people.
+    pub fn at_least(&self, k: usize) -> Questions {
+        self.matching(|count| count >= k)
+    }
+
+    /// Questions answered by exactly `k` people.
+    pub fn exactly(&self, k: usize) -> Questions {
+        self.matching(|count| count == k)
+    }
+
+    fn matching(&self, keep: impl Fn(usize) -> bool) -> Questions {
+        let set = self.counts()
+                      .iter()
+                      .enumerate()
+                      .filter(|&(_, &count)| keep(count))
+                      .fold(0, |set, (question, _)| set | 1 << question);
+        Questions(set)
+    }
+}
+
+pub type Parsed = Vec<Group>;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    let mut groups = Vec::new();
+    let mut people = Vec::new();
+    // `lines` drops the '\r' of "\r\n" too, and any run of blank lines ends
+    // a group, so blank lines at the end don't make an empty one
+    for person in input.lines().map(str::trim) {
+        if person.is_empty() {
+            if !people.is_empty() {
+                groups.push(Group { people: std::mem::take(&mut people) });
+            }
+            continue;
+        }
+        match person.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            Some((i, _)) => return Err(ParseError::at(input, &person[i..], Expected::Question)),
+            None => people.push(Questions::from_answers(person)),
+        }
+    }
+    if !people.is_empty() {
+        groups.push(Group { people });
+    }
+    Ok(groups)
+}
+
+pub fn solve(input: &str) -> Result<Answers, ParseError> {
+    let groups = parse(input)?;
+    Ok(Answers::new(part_1(&groups), part_2(&groups)))
+}
+
+pub fn part_1(groups: &[Group]) -> usize {
+    count(groups, &Query::Anyone)
+}
+
+pub fn part_2(groups: &[Group]) -> usize {
+    count(groups, &Query::Everyone)
+}
+
+/// A set expression picking questions out of a group, e.g.
+/// `at-least 2 - all` or `!(any | [xyz])`. `&` binds tighter than `|` and `-`,
+/// which go left to right.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    Anyone,
+    Everyone,
+    Nobody,
+    AtLeast(usize),
+    Exactly(usize),
+    Questions(Questions),
+    Not(Box<Query>),
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Minus(Box<Query>, Box<Query>),
+}
+
+impl Query {
+    pub fn parse(query: &str) -> Result<Query, ParseError> {
+        let mut reader = QueryReader { query, at: 0 };
+        let parsed = reader.expression()?;
+        match reader.peek() {
+            Some(_) => Err(reader.error(Expected::EndOfQuery)),
+            None => Ok(parsed),
+        }
+    }
+
+    pub fn evaluate(&self, group: &Group) -> Questions {
+        match self {
+            Query::Anyone => group.anyone(),
+            Query::Everyone => group.everyone(),
+            Query::Nobody => group.nobody(),
+            Query::AtLeast(k) => group.at_least(*k),
+            Query::Exactly(k) => group.exactly(*k),
+            Query::Questions(questions) => *questions,
+            Query::Not(query) => !query.evaluate(group),
+            Query::And(a, b) => a.evaluate(group) & b.evaluate(group),
+            Query::Or(a, b) => a.evaluate(group) | b.evaluate(group),
+            Query::Minus(a, b) => a.evaluate(group) - b.evaluate(group),
+        }
+    }
+}
+
+// a recursive descent over the query, `at` is the byte offset read up to
+struct QueryReader<'a> {
+    query: &'a str,
+    at: usize,
+}
+
+impl<'a> QueryReader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.query[self.at..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.at += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    fn error(&mut self, expected: Expected) -> ParseError {
+        self.skip_whitespace();
+        ParseError::at(self.query, self.rest(), expected)
+    }
+
+    fn expression(&mut self) -> Result<Query, ParseError> {
+        let mut query = self.term()?;
+        while let Some(op @ ('|' | '-')) = self.peek() {
+            self.at += 1;
+            let other = Box::new(self.term()?);
+            query = match op {
+                '|' => Query::Or(Box::new(query), other),
+                _ => Query::Minus(Box::new(query), other),
+            };
+        }
+        Ok(query)
+    }
+
+    fn term(&mut self) -> Result<Query, ParseError> {
+        let mut query = self.unary()?;
+        while let Some('&') = self.peek() {
+            self.at += 1;
+            query = Query::And(Box::new(query), Box::new(self.unary()?));
+        }
+        Ok(query)
+    }
+
+    fn unary(&mut self) -> Result<Query, ParseError> {
+        match self.peek() {
+            Some('!') => {
+                self.at += 1;
+                Ok(Query::Not(Box::new(self.unary()?)))
+            },
+            Some('(') => {
+                self.at += 1;
+                let query = self.expression()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.at += 1;
+                        Ok(query)
+                    },
+                    _ => Err(self.error(Expected::CloseParen)),
+                }
+            },
+            Some('[') => {
+                self.at += 1;
+                let questions = self.word(|c| c.is_ascii_lowercase());
+                match self.rest().chars().next() {
+                    Some(']') => {
+                        self.at += 1;
+                        Ok(Query::Questions(Questions::from_answers(questions)))
+                    },
+                    Some(_) => Err(self.error(Expected::Question)),
+                    None => Err(self.error(Expected::CloseBracket)),
+                }
+            },
+            _ => {
+                let start = self.at;
+                // words stop at '-' so `any-all` is a difference, `at-least`
+                // is the one name with a '-' in it
+                match self.word(|c| c.is_ascii_alphabetic()) {
+                    "any" => Ok(Query::Anyone),
+                    "all" => Ok(Query::Everyone),
+                    "none" => Ok(Query::Nobody),
+                    "at" if self.rest().starts_with("-least") => {
+                        self.at += "-least".len();
+                        Ok(Query::AtLeast(self.number()?))
+                    },
+                    "exactly" => Ok(Query::Exactly(self.number()?)),
+                    _ => {
+                        self.at = start;
+                        Err(self.error(Expected::Term))
+                    },
+                }
+            },
+        }
+    }
+
+    fn number(&mut self) -> Result<usize, ParseError> {
+        self.skip_whitespace();
+        self.word(|c| c.is_ascii_digit())
+            .parse()
+            .map_err(|_| self.error(Expected::Number))
+    }
+
+    fn word(&mut self, part_of: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let end = rest.find(|c| !part_of(c)).unwrap_or(rest.len());
+        self.at += end;
+        &rest[..end]
+    }
+}
+
+/// How many questions `query` picks out, summed over every group.
+pub fn count(groups: &[Group], query: &Query) -> usize {
+    groups.iter()
+          .map(|group| query.evaluate(group).len())
+          .sum()
+}
+
+/// For each question, 'a' first, how many groups `query` picks it out in.
+pub fn histogram(groups: &[Group], query: &Query) -> [usize; 26] {
+    let mut histogram = [0; 26];
+    for group in groups {
+        for question in query.evaluate(group).iter() {
+            histogram[(question as u8 - b'a') as usize] += 1;
+        }
+    }
+    histogram
+}
+
+/// One bar per question, scaled so the longest is `width` wide.
+pub fn render_histogram(histogram: &[usize; 26], width: usize) -> String {
+    let most = histogram.iter().copied().max().unwrap_or(0).max(1);
+    ('a'..='z').zip(histogram)
+               .map(|(question, &count)| format!("{} {:>5} {}", question, count, "#".repeat(count * width / most)))
+               .collect::<Vec<String>>()
+               .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_tests() {
+        let groups = parse("abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb").unwrap();
+        assert_eq!(parse("abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb\n\n").unwrap(), groups);
+        assert_eq!(parse("abc\r\n\r\na\r\nb\r\nc\r\n\r\n\r\nab\r\nac\r\n\r\na\na\na\na\n\nb\r\n").unwrap(), groups);
+        assert_eq!(count(&parse("abc\n\nab\n\n").unwrap(), &Query::Nobody), 47);
+        let err = parse("abc\r\n\r\naB\r\n").err().unwrap();
+        assert_eq!(err.to_string(), "line 3, column 2: expected a question from 'a' to 'z', found 'B'");
+        assert_eq!(groups[2].people, vec![Questions::from_answers("ab"), Questions::from_answers("ac")]);
+        assert_eq!(part_1(&groups), 11);
+        assert_eq!(part_2(&groups), 6);
+
+        let group = &groups[2];
+        assert_eq!(group.anyone().to_string(), "abc");
+        assert_eq!(group.everyone().to_string(), "a");
+        assert_eq!(group.exactly(1).to_string(), "bc");
+        assert_eq!(group.at_least(2).to_string(), "a");
+        assert_eq!(group.nobody().len(), 23);
+        assert!(!group.nobody().contains('a') && group.nobody().contains('z'));
+
+        let query = |query: &str| count(&groups, &Query::parse(query).unwrap());
+        assert_eq!(query("any"), 11);
+        assert_eq!(query("all"), 6);
+        assert_eq!(query("none"), 26 * 5 - 11);
+        assert_eq!(query("exactly 1"), 9);
+        assert_eq!(query("at-least 2"), 2);
+        assert_eq!(query("any - all"), 5);
+        assert_eq!(query("any-all"), 5);
+        assert_eq!(query("at-least 1-all"), 5);
+        assert_eq!(Query::parse("all-at-least 2").unwrap(),
+                   Query::Minus(Box::new(Query::Everyone), Box::new(Query::AtLeast(2))));
+        assert_eq!(query("!none"), 11);
+        assert_eq!(query("any & [ab]"), 8);
+        assert_eq!(query("[a] | [b] & all"), 7);
+        assert_eq!(query("([a] | [b]) & all"), 5);
+        assert_eq!(query("any - [a] - [b]"), 3);
+
+        let err = Query::parse("any & (all | sometimes)").err().unwrap();
+        assert_eq!((err.column, err.expected), (14, Expected::Term));
+        let err = Query::parse("exactly two").err().unwrap();
+        assert_eq!((err.column, err.expected), (9, Expected::Number));
+        let err = Query::parse("(any").err().unwrap();
+        assert_eq!((err.column, err.expected), (5, Expected::CloseParen));
+        let err = Query::parse("[aB]").err().unwrap();
+        assert_eq!((err.column, err.expected), (3, Expected::Question));
+        let err = Query::parse("[ab").err().unwrap();
+        assert_eq!((err.column, err.expected), (4, Expected::CloseBracket));
+        let err = Query::parse("any all").err().unwrap();
+        assert_eq!((err.column, err.expected), (5, Expected::EndOfQuery));
+        let err = Query::parse("at-most 2").err().unwrap();
+        assert_eq!((err.column, err.expected), (1, Expected::Term));
+
+        let any = histogram(&groups, &Query::Anyone);
+        assert_eq!(&any[..4], &[4, 4, 3, 0]);
+        assert_eq!(render_histogram(&any, 8).lines().take(4).collect::<Vec<&str>>(),
+                   vec!["a     4 ########", "b     4 ########", "c     3 ######", "d     0 "]);
+    }
+}
@@ -0,0 +1,381 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use common::Answers;
+
+const SHINY_GOLD: &str = "shiny gold";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Contain,
+    FullStop,
+    Count,
+    Bag,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Contain => write!(f, "'<colour> bags contain '"),
+            Expected::FullStop => write!(f, "'.'"),
+            Expected::Count => write!(f, "a number of bags"),
+            Expected::Bag => write!(f, "'<colour> bag' or '<colour> bags'"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+/// Why a question about the rules has no answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BagError {
+    Unknown(String),
+    /// Bags that end up inside themselves, starting and ending with the same bag.
+    Cycle(Vec<String>),
+    /// More bags end up inside this one than a `u64` can count.
+    Overflow(String),
+}
+
+impl fmt::Display for BagError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BagError::Unknown(bag) => write!(f, "no rule mentions {} bags", bag),
+            BagError::Cycle(bags) => write!(f, "the rules go round in a circle: {}", bags.join(" -> ")),
+            BagError::Overflow(bag) => write!(f, "too many bags inside {} bags to count", bag),
+        }
+    }
+}
+
+/// The rules as a graph, each bag pointing at the bags it has to contain and,
+/// the other way, at the bags it can be put in.
+#[derive(Debug)]
+pub struct Bags {
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+    contents: Vec<Vec<(usize, u64)>>,
+    containers: Vec<Vec<usize>>,
+}
+
+pub type Parsed = Bags;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    let mut bags = Bags { names: Vec::new(), index: HashMap::new(), contents: Vec::new(), containers: Vec::new() };
+    for rule in input.trim().split('\n') {
+        let (name, contents) = read_rule(input, rule)?;
+        let bag = bags.add(name);
+        for (count, child) in contents {
+            let child = bags.add(child);
+            bags.contents[bag].push((child, count));
+            bags.containers[child].push(bag);
+        }
+    }
+    Ok(bags)
+}
+
+// a bag's colour and how many of which coloured bags it holds
+type Rule<'a> = (&'a str, Vec<(u64, &'a str)>);
+
+fn read_rule<'a>(input: &str, rule: &'a str) -> Result<Rule<'a>, ParseError> {
+    let (name, children) = rule.split_once(" bags contain ")
+                               .ok_or_else(|| ParseError::at(input, rule, Expected::Contain))?;
+    let children = children.strip_suffix('.')
+                           .ok_or_else(|| ParseError::at(input, &children[children.len()..], Expected::FullStop))?;
+    if children == "no other bags" {
+        return Ok((name, Vec::new()));
+    }
+    let contents = children.split(", ")
+                           .map(|child| {
+                               let (count, bag) = child.split_once(' ').unwrap_or((child, ""));
+                               let count = count.parse::<u64>()
+                                                .map_err(|_| ParseError::at(input, count, Expected::Count))?;
+                               let colour = bag.strip_suffix(" bags")
+                                               .or_else(|| bag.strip_suffix(" bag"))
+                                               .ok_or_else(|| ParseError::at(input, bag, Expected::Bag))?;
+                               Ok((count, colour))
+                           })
+                           .collect::<Result<Vec<(u64, &str)>, ParseError>>()?;
+    Ok((name, contents))
+}
+
+pub fn solve(input: &str) -> Result<Answers, String> {
+    let bags = parse(input).map_err(|err| err.to_string())?;
+    Ok(Answers::new(part_1(&bags), part_2(&bags).map_err(|err| err.to_string())?))
+}
+
+pub fn part_1(bags: &Bags) -> usize {
+    // with a cycle the bag turns up among its own containers, which doesn't count
+    bags.containers_of(SHINY_GOLD)
+        .map_or(0, |containers| containers.iter().filter(|&&bag| bag != SHINY_GOLD).count())
+}
+
+pub fn part_2(bags: &Bags) -> Result<u64, BagError> {
+    bags.count_inside(SHINY_GOLD)
+}
+
+#[derive(Clone, Copy)]
+enum Visit {
+    New,
+    Open,
+    // `None` when there are too many to count
+    Counted(Option<u64>),
+}
+
+impl Bags {
+    // the bag's index, adding it if it hasn't come up before
+    fn add(&mut self, name: &str) -> usize {
+        if let Some(&bag) = self.index.get(name) {
+            return bag;
+        }
+        self.names.push(name.to_owned());
+        self.contents.push(Vec::new());
+        self.containers.push(Vec::new());
+        self.index.insert(name.to_owned(), self.names.len() - 1);
+        self.names.len() - 1
+    }
+
+    fn find(&self, name: &str) -> Result<usize, BagError> {
+        self.index.get(name).copied().ok_or_else(|| BagError::Unknown(name.to_owned()))
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(|name| name.as_str())
+    }
+
+    /// The bags `name` has to contain directly, with how many of each.
+    pub fn contents(&self, name: &str) -> Result<Vec<(&str, u64)>, BagError> {
+        let bag = self.find(name)?;
+        Ok(self.contents[bag].iter().map(|&(child, count)| (self.names[child].as_str(), count)).collect())
+    }
+
+    /// Every bag that ends up holding `name`, however deep down, in the order
+    /// they're found going outwards.
+    pub fn containers_of(&self, name: &str) -> Result<Vec<&str>, BagError> {
+        let bag = self.find(name)?;
+        let mut seen = vec![false; self.names.len()];
+        let mut queue = VecDeque::from(vec![bag]);
+        let mut containers = Vec::new();
+        while let Some(bag) = queue.pop_front() {
+            for &container in &self.containers[bag] {
+                if !seen[container] {
+                    seen[container] = true;
+                    containers.push(self.names[container].as_str());
+                    queue.push_back(container);
+                }
+            }
+        }
+        Ok(containers)
+    }
+
+    /// How many bags `name` holds in total. Each bag's count is worked out
+    /// once, and a bag that ends up inside itself or holds more bags than a
+    /// `u64` can count is an error.
+    pub fn count_inside(&self, name: &str) -> Result<u64, BagError> {
+        let bag = self.find(name)?;
+        let mut visits = vec![Visit::New; self.names.len()];
+        self.count(bag, &mut visits)?.ok_or_else(|| BagError::Overflow(name.to_owned()))
+    }
+
+    // a depth first walk down from `bag` with its own stack, so long chains of
+    // bags don't run out of call stack. Each frame is a bag, how far through
+    // its contents it's got and the count so far.
+    fn count(&self, bag: usize, visits: &mut [Visit]) -> Result<Option<u64>, BagError> {
+        if let Visit::Counted(count) = visits[bag] {
+            return Ok(count);
+        }
+        visits[bag] = Visit::Open;
+        let mut stack = vec![(bag, 0, Some(0))];
+        while let Some(&(top, next, total)) = stack.last() {
+            let (child, count) = match self.contents[top].get(next) {
+                Some(&content) => content,
+                None => {
+                    stack.pop();
+                    visits[top] = Visit::Counted(total);
+                    continue;
+                },
+            };
+            match visits[child] {
+                Visit::New => {
+                    visits[child] = Visit::Open;
+                    stack.push((child, 0, Some(0)));
+                },
+                Visit::Open => {
+                    let path = stack.iter().map(|&(bag, _, _)| bag).collect::<Vec<usize>>();
+                    return Err(self.cycle(&path, child));
+                },
+                Visit::Counted(inside) => {
+                    let total = total.zip(inside)
+                                     .and_then(|(total, inside)| inside.checked_add(1)?.checked_mul(count)?.checked_add(total));
+                    *stack.last_mut().unwrap() = (top, next + 1, total);
+                },
+            }
+        }
+        match visits[bag] {
+            Visit::Counted(count) => Ok(count),
+            _ => unreachable!("the walk finishes every bag it opens"),
+        }
+    }
+
+    // the part of `path` from `bag` on, back round to `bag`
+    fn cycle(&self, path: &[usize], bag: usize) -> BagError {
+        let start = path.iter().position(|&b| b == bag).unwrap();
+        let names = path[start..].iter()
+                                 .chain(Some(&bag))
+                                 .map(|&b| self.names[b].clone())
+                                 .collect();
+        BagError::Cycle(names)
+    }
+
+    /// The first circle of bags ending up inside themselves, if there is one.
+    pub fn find_cycle(&self) -> Option<BagError> {
+        let mut visits = vec![Visit::New; self.names.len()];
+        (0..self.names.len()).find_map(|bag| self.count(bag, &mut visits).err())
+    }
+
+    /// The shortest chain of bags from `outer` down to `inner`, each holding
+    /// the next, or `None` if `inner` never ends up in `outer`.
+    pub fn path(&self, inner: &str, outer: &str) -> Result<Option<Vec<&str>>, BagError> {
+        let (inner, outer) = (self.find(inner)?, self.find(outer)?);
+        let mut parent: Vec<Option<usize>> = vec![None; self.names.len()];
+        let mut seen = vec![false; self.names.len()];
+        seen[outer] = true;
+        let mut queue = VecDeque::from(vec![outer]);
+        while let Some(bag) = queue.pop_front() {
+            for &(child, _) in &self.contents[bag] {
+                // checked before `seen` so a bag can be found inside itself
+                if child == inner {
+                    let mut path = vec![self.names[inner].as_str(), self.names[bag].as_str()];
+                    let mut at = bag;
+                    while let Some(container) = parent[at] {
+                        path.push(self.names[container].as_str());
+                        at = container;
+                    }
+                    path.reverse();
+                    return Ok(Some(path));
+                }
+                if !seen[child] {
+                    seen[child] = true;
+                    parent[child] = Some(bag);
+                    queue.push_back(child);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// The rules as a Graphviz digraph, an edge from each bag to the bags it
+    /// holds labelled with how many.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph bags {\n");
+        for name in &self.names {
+            dot.push_str(&format!("    {};\n", quoted(name)));
+        }
+        for (bag, contents) in self.contents.iter().enumerate() {
+            for &(child, count) in contents {
+                dot.push_str(&format!("    {} -> {} [label={}];\n", quoted(&self.names[bag]), quoted(&self.names[child]), count));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// a Graphviz ID for the bag, with quotes and backslashes escaped so any
+// colour can be used
+fn quoted(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+    #[test]
+    fn part_1_tests() {
+        let bags = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&bags), 4);
+        assert_eq!(bags.containers_of(SHINY_GOLD).unwrap(), vec!["bright white", "muted yellow", "light red", "dark orange"]);
+        assert_eq!(bags.containers_of("light red").unwrap(), Vec::<&str>::new());
+        assert_eq!(bags.contents("muted yellow").unwrap(), vec![("shiny gold", 2), ("faded blue", 9)]);
+        assert_eq!(bags.containers_of("mauve"), Err(BagError::Unknown("mauve".to_owned())));
+
+        let err = parse("light red bags contain 1 bright white bag, two muted yellow bags.").err().unwrap();
+        assert_eq!((err.column, err.expected), (44, Expected::Count));
+        let err = parse("light red bags contain 1 bright white box.").err().unwrap();
+        assert_eq!((err.column, err.expected), (26, Expected::Bag));
+    }
+
+    #[test]
+    fn part_2_tests() {
+        let bags = parse("\
+shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.").unwrap();
+        assert_eq!(bags.count_inside(SHINY_GOLD), Ok(126));
+
+        // each bag holds two of the next, far too many to count by the end
+        let rules = (0..70).map(|i| format!("dark b{} bags contain 2 dark b{} bags.", i, i + 1)).collect::<Vec<String>>();
+        let bags = parse(&(rules.join("\n") + "\ndark b70 bags contain no other bags.")).unwrap();
+        assert_eq!(bags.count_inside("dark b10"), Ok((1 << 61) - 2));
+        assert_eq!(bags.count_inside("dark b0"), Err(BagError::Overflow("dark b0".to_owned())));
+        assert_eq!(bags.count_inside("dark b0").unwrap_err().to_string(), "too many bags inside dark b0 bags to count");
+        assert_eq!(bags.find_cycle(), None);
+
+        // deeper than the call stack would go
+        let rules = (0..200_000).map(|i| format!("dark b{} bags contain 1 dark b{} bag.", i, i + 1)).collect::<Vec<String>>();
+        let bags = parse(&(rules.join("\n") + "\ndark b200000 bags contain no other bags.")).unwrap();
+        assert_eq!(bags.count_inside("dark b0"), Ok(200_000));
+        assert_eq!(part_2(&parse(EXAMPLE).unwrap()), Ok(32));
+    }
+
+    #[test]
+    fn graph_tests() {
+        let bags = parse(EXAMPLE).unwrap();
+        assert_eq!(bags.find_cycle(), None);
+        assert_eq!(bags.path(SHINY_GOLD, "light red").unwrap(), Some(vec!["light red", "bright white", "shiny gold"]));
+        assert_eq!(bags.path("dotted black", "muted yellow").unwrap(),
+                   Some(vec!["muted yellow", "shiny gold", "dark olive", "dotted black"]));
+        assert_eq!(bags.path("light red", SHINY_GOLD).unwrap(), None);
+
+        let cyclic = parse("\
+shiny gold bags contain 1 dark red bag, 2 faded blue bags.
+dark red bags contain 3 dark orange bags.
+dark orange bags contain 1 shiny gold bag.
+light red bags contain 1 dark orange bag.
+faded blue bags contain no other bags.").unwrap();
+        let cycle = BagError::Cycle(vec!["shiny gold", "dark red", "dark orange", "shiny gold"].into_iter().map(String::from).collect());
+        assert_eq!(cyclic.count_inside(SHINY_GOLD), Err(cycle.clone()));
+        assert_eq!(cyclic.find_cycle(), Some(cycle));
+        assert_eq!(cyclic.count_inside("faded blue"), Ok(0));
+        assert_eq!(cyclic.containers_of(SHINY_GOLD).unwrap().len(), 4);
+        assert_eq!(part_1(&cyclic), 3);
+        assert_eq!(part_2(&cyclic).unwrap_err().to_string(),
+                   "the rules go round in a circle: shiny gold -> dark red -> dark orange -> shiny gold");
+        assert_eq!(cyclic.path(SHINY_GOLD, SHINY_GOLD).unwrap(), Some(vec!["shiny gold", "dark red", "dark orange", "shiny gold"]));
+        assert_eq!(cyclic.path("light red", SHINY_GOLD).unwrap(), None);
+
+        let bags = parse("a b bags contain 2 c d bags.\nc d bags contain no other bags.").unwrap();
+        assert_eq!(bags.to_dot(), "digraph bags {\n    \"a b\";\n    \"c d\";\n    \"a b\" -> \"c d\" [label=2];\n}\n");
+        let bags = parse(r#"say "hi" bags contain 1 back\slash bag."#).unwrap();
+        assert_eq!(bags.to_dot(), r#"digraph bags {
+    "say \"hi\"";
+    "back\\slash";
+    "say \"hi\"" -> "back\\slash" [label=1];
+}
+"#);
+    }
+}
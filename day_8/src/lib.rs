@@ -0,0 +1,762 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::convert::TryFrom;
+use std::fmt;
+
+use common::Answers;
+
+/// What the machine holds between instructions. `ip` can be pushed anywhere by
+/// a jump, the machine only checks it before running the next instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Registers {
+    pub acc: i64,
+    pub ip: i64,
+}
+
+/// Runs an instruction with its argument, moving `ip` on as it sees fit.
+pub type Execute = fn(&mut Registers, i64);
+
+/// The operations a program can use, looked up by name when parsing.
+#[derive(Debug, Clone)]
+pub struct Opcodes {
+    names: Vec<String>,
+    executes: Vec<Execute>,
+}
+
+impl Opcodes {
+    pub const ACC: usize = 0;
+    pub const JMP: usize = 1;
+    pub const NOP: usize = 2;
+
+    /// `acc`, `jmp` and `nop`, in that order.
+    pub fn standard() -> Opcodes {
+        let mut opcodes = Opcodes { names: Vec::new(), executes: Vec::new() };
+        opcodes.register("acc", |registers, arg| {
+            registers.acc += arg;
+            registers.ip += 1;
+        });
+        opcodes.register("jmp", |registers, arg| registers.ip += arg);
+        opcodes.register("nop", |registers, _| registers.ip += 1);
+        opcodes
+    }
+
+    /// Adds an operation, or replaces the one with the same name, and returns
+    /// its opcode.
+    pub fn register(&mut self, name: &str, execute: Execute) -> usize {
+        match self.find(name) {
+            Some(op) => {
+                self.executes[op] = execute;
+                op
+            },
+            None => {
+                self.names.push(name.to_owned());
+                self.executes.push(execute);
+                self.names.len() - 1
+            },
+        }
+    }
+
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|known| known == name)
+    }
+
+    pub fn name(&self, op: usize) -> &str {
+        &self.names[op]
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Instruction {
+    pub op: usize,
+    pub arg: i64,
+}
+
+/// Boot code and the operations it was written with.
+#[derive(Debug, Clone)]
+pub struct Program {
+    pub opcodes: Opcodes,
+    pub code: Vec<Instruction>,
+}
+
+impl Program {
+    pub fn instruction(&self, instruction: &Instruction) -> String {
+        format!("{} {:+}", self.opcodes.name(instruction.op), instruction.arg)
+    }
+
+    /// Every instruction with its address, and where the standard jumps go.
+    pub fn disassemble(&self) -> String {
+        self.code.iter()
+                 .enumerate()
+                 .map(|(ip, instruction)| self.listing_line(ip, instruction))
+                 .collect::<Vec<String>>()
+                 .join("\n")
+    }
+
+    fn listing_line(&self, ip: usize, instruction: &Instruction) -> String {
+        let line = format!("{:>5}  {}", ip, self.instruction(instruction));
+        match instruction.op {
+            Opcodes::JMP => format!("{:<20}-> {}", line, ip as i64 + instruction.arg),
+            _ => line,
+        }
+    }
+}
+
+/// The program as source, one instruction per line.
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for instruction in &self.code {
+            writeln!(f, "{}", self.instruction(instruction))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Operation(Vec<String>),
+    Argument,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Operation(names) => {
+                let names = names.iter().map(|name| format!("'{}'", name)).collect::<Vec<String>>();
+                match names.split_last() {
+                    Some((last, [])) => write!(f, "{}", last),
+                    Some((last, rest)) => write!(f, "{} or {}", rest.join(", "), last),
+                    None => write!(f, "an operation"),
+                }
+            },
+            Expected::Argument => write!(f, "a signed number"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Program;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    parse_with(input, Opcodes::standard())
+}
+
+pub fn parse_with(input: &str, opcodes: Opcodes) -> Result<Parsed, ParseError> {
+    let code = input.trim()
+                    .split('\n')
+                    .map(|instruction| {
+                        let (operation, argument) = instruction.split_once(' ')
+                                                               .unwrap_or((instruction, &instruction[instruction.len()..]));
+                        Ok(Instruction {
+                            op: opcodes.find(operation)
+                                       .ok_or_else(|| ParseError::at(input, operation, Expected::Operation(opcodes.names.clone())))?,
+                            arg: argument.parse::<i64>().map_err(|_| ParseError::at(input, argument, Expected::Argument))?,
+                        })
+                    })
+                    .collect::<Result<Vec<Instruction>, ParseError>>()?;
+    Ok(Program { opcodes, code })
+}
+
+pub fn solve(input: &str) -> Result<Answers, String> {
+    let program = parse(input).map_err(|err| err.to_string())?;
+    Ok(Answers::new(part_1(&program), part_2(&program)?))
+}
+
+/// One executed instruction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Step {
+    pub ip: usize,
+    pub instruction: Instruction,
+    pub before: Registers,
+    pub after: Registers,
+}
+
+/// Why the machine stopped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stop {
+    /// Ran off the end of the program, the way boot code should finish.
+    Terminated,
+    /// Jumped somewhere other than an instruction or just past the last one.
+    OutOfBounds(i64),
+    /// About to run an instruction a second time.
+    Looped(usize),
+    Breakpoint(usize),
+    /// The accumulator changed while it was being watched.
+    Watch { from: i64, to: i64 },
+    /// Ran as many instructions as it was allowed to.
+    Limit(usize),
+    /// Ran the instructions it was asked to step through.
+    Stepped,
+}
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stop::Terminated => write!(f, "terminated"),
+            Stop::OutOfBounds(ip) => write!(f, "jumped out of the program to {}", ip),
+            Stop::Looped(ip) => write!(f, "about to run {} a second time", ip),
+            Stop::Breakpoint(ip) => write!(f, "breakpoint at {}", ip),
+            Stop::Watch { from, to } => write!(f, "acc changed from {} to {}", from, to),
+            Stop::Limit(count) => write!(f, "hit the limit of {} instructions", count),
+            Stop::Stepped => write!(f, "stepped"),
+        }
+    }
+}
+
+/// A handheld console running a program, keeping a trace of what it ran.
+pub struct Vm<'a> {
+    program: &'a Program,
+    registers: Registers,
+    visited: Vec<bool>,
+    trace: Vec<Step>,
+    breakpoints: BTreeSet<usize>,
+    watch: bool,
+    limit: Option<usize>,
+}
+
+impl<'a> Vm<'a> {
+    pub fn new(program: &'a Program) -> Vm<'a> {
+        Vm {
+            program,
+            registers: Registers::default(),
+            visited: vec![false; program.code.len()],
+            trace: Vec::new(),
+            breakpoints: BTreeSet::new(),
+            watch: false,
+            limit: None,
+        }
+    }
+
+    pub fn program(&self) -> &Program {
+        self.program
+    }
+
+    pub fn registers(&self) -> Registers {
+        self.registers
+    }
+
+    pub fn trace(&self) -> &[Step] {
+        &self.trace
+    }
+
+    /// Starts the program over, keeping breakpoints, the watch and the limit.
+    pub fn reset(&mut self) {
+        self.registers = Registers::default();
+        self.visited = vec![false; self.program.code.len()];
+        self.trace.clear();
+    }
+
+    pub fn set_breakpoint(&mut self, ip: usize) {
+        self.breakpoints.insert(ip);
+    }
+
+    pub fn clear_breakpoint(&mut self, ip: usize) -> bool {
+        self.breakpoints.remove(&ip)
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    pub fn watch_accumulator(&mut self, watch: bool) {
+        self.watch = watch;
+    }
+
+    /// How many instructions the machine may run in total before it stops.
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+    }
+
+    // the instruction about to run, or why there isn't one
+    fn next(&self) -> Result<usize, Stop> {
+        let ip = self.registers.ip;
+        match ip {
+            ip if ip == self.program.code.len() as i64 => Err(Stop::Terminated),
+            ip if ip < 0 || ip > self.program.code.len() as i64 => Err(Stop::OutOfBounds(ip)),
+            _ => Ok(ip as usize),
+        }
+    }
+
+    fn execute(&mut self, ip: usize) -> Option<Stop> {
+        if let Some(limit) = self.limit.filter(|&limit| self.trace.len() >= limit) {
+            return Some(Stop::Limit(limit));
+        }
+        let instruction = self.program.code[ip];
+        let before = self.registers;
+        (self.program.opcodes.executes[instruction.op])(&mut self.registers, instruction.arg);
+        self.visited[ip] = true;
+        self.trace.push(Step { ip, instruction, before, after: self.registers });
+        match self.watch && before.acc != self.registers.acc {
+            true => Some(Stop::Watch { from: before.acc, to: self.registers.acc }),
+            false => None,
+        }
+    }
+
+    /// Runs `count` instructions, stopping early at anything `run` would stop
+    /// at except breakpoints and loops.
+    pub fn step(&mut self, count: usize) -> Stop {
+        for _ in 0..count {
+            let ip = match self.next() {
+                Ok(ip) => ip,
+                Err(stop) => return stop,
+            };
+            if let Some(stop) = self.execute(ip) {
+                return stop;
+            }
+        }
+        self.next().err().unwrap_or(Stop::Stepped)
+    }
+
+    /// Runs until the program ends or something stops it. The instruction it
+    /// starts on always runs, so running again carries on past a breakpoint.
+    pub fn run(&mut self) -> Stop {
+        let mut first = true;
+        loop {
+            let ip = match self.next() {
+                Ok(ip) => ip,
+                Err(stop) => return stop,
+            };
+            if !first && self.breakpoints.contains(&ip) {
+                return Stop::Breakpoint(ip);
+            }
+            if !first && self.visited[ip] {
+                return Stop::Looped(ip);
+            }
+            first = false;
+            if let Some(stop) = self.execute(ip) {
+                return stop;
+            }
+        }
+    }
+}
+
+// the operation a corrupted instruction might have been instead
+fn swapped(op: usize) -> Option<usize> {
+    match op {
+        Opcodes::JMP => Some(Opcodes::NOP),
+        Opcodes::NOP => Some(Opcodes::JMP),
+        _ => None,
+    }
+}
+
+pub fn part_1(program: &Program) -> i64 {
+    let mut vm = Vm::new(program);
+    vm.run();
+    vm.registers().acc
+}
+
+pub fn part_2(program: &Program) -> Result<i64, String> {
+    match repairs(program)?.first() {
+        Some(repair) => Ok(repair.acc),
+        None => Err("no single change lets the boot code finish".to_owned()),
+    }
+}
+
+/// A `jmp` or `nop` that lets the boot code finish once it's swapped for the
+/// other, and the accumulator it finishes with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Repair {
+    pub ip: usize,
+    pub instruction: Instruction,
+    pub op: usize,
+    pub acc: i64,
+}
+
+impl Repair {
+    pub fn describe(&self, program: &Program) -> String {
+        let repaired = Instruction { op: self.op, ..self.instruction };
+        format!("change '{}' at {} to '{}' and it finishes with acc {}",
+                program.instruction(&self.instruction), self.ip, program.instruction(&repaired), self.acc)
+    }
+}
+
+/// Every single swap of a `jmp` for a `nop` or back that lets the boot code
+/// finish, in the order the broken program runs into them.
+///
+/// Works backwards from the end of the program to find every instruction
+/// that already leads there, then follows the program once from the start:
+/// a swap works when it sends the program to one of those instructions. Only
+/// `acc`, `jmp` and `nop` are understood, since other operations can do
+/// anything to `ip` and `acc`.
+pub fn repairs(program: &Program) -> Result<Vec<Repair>, String> {
+    let code = &program.code;
+    if let Some((ip, instruction)) = code.iter().enumerate().find(|(_, instruction)| instruction.op > Opcodes::NOP) {
+        return Err(format!("can't analyse '{}' at {}, only acc, jmp and nop", program.instruction(instruction), ip));
+    }
+    // where an instruction sends the program, if that's still in it or just past its end
+    let next = |ip: usize, op: usize, arg: i64| {
+        let next = match op {
+            Opcodes::JMP => ip as i64 + arg,
+            _ => ip as i64 + 1,
+        };
+        match (0..=code.len() as i64).contains(&next) {
+            true => Some(next as usize),
+            false => None,
+        }
+    };
+
+    let mut sources = vec![Vec::new(); code.len() + 1];
+    for (ip, instruction) in code.iter().enumerate() {
+        if let Some(next) = next(ip, instruction.op, instruction.arg) {
+            sources[next].push(ip);
+        }
+    }
+    // how much each instruction that leads to the end adds to acc on the way
+    let mut to_end: Vec<Option<i64>> = vec![None; code.len() + 1];
+    to_end[code.len()] = Some(0);
+    let mut queue = VecDeque::from(vec![code.len()]);
+    while let Some(target) = queue.pop_front() {
+        for &ip in &sources[target] {
+            let gain = match code[ip].op {
+                Opcodes::ACC => code[ip].arg,
+                _ => 0,
+            };
+            to_end[ip] = Some(gain + to_end[target].unwrap());
+            queue.push_back(ip);
+        }
+    }
+    if to_end[0].is_some() {
+        return Err("the boot code already finishes".to_owned());
+    }
+
+    let mut repairs = Vec::new();
+    let mut seen = vec![false; code.len()];
+    let mut ip = 0;
+    let mut acc = 0;
+    while !seen[ip] {
+        seen[ip] = true;
+        let instruction = code[ip];
+        if let Some(op) = swapped(instruction.op) {
+            if let Some(rest) = next(ip, op, instruction.arg).and_then(|next| to_end[next]) {
+                repairs.push(Repair { ip, instruction, op, acc: acc + rest });
+            }
+        }
+        if instruction.op == Opcodes::ACC {
+            acc += instruction.arg;
+        }
+        // the end can't be reached from here, so the program stays inside it or jumps out
+        match next(ip, instruction.op, instruction.arg) {
+            Some(next) => ip = next,
+            None => break,
+        }
+    }
+    Ok(repairs)
+}
+
+/// Drives a `Vm` from typed commands, see `HELP`.
+pub struct Debugger<'a> {
+    vm: Vm<'a>,
+}
+
+pub const HELP: &str = "\
+step [n]          run the next n instructions, 1 by default
+continue          run until the program ends, loops or hits a breakpoint
+break <ip>        stop before running <ip>
+delete <ip>       remove the breakpoint at <ip>
+watch             stop whenever acc changes, again to stop watching
+limit [n]         stop after n instructions in total, no limit without n
+registers         show acc and ip
+list [ip] [n]     disassemble n instructions around ip, the current one by default
+trace [n]         the last n instructions run, 10 by default
+reset             start over, keeping breakpoints, the watch and the limit
+quit";
+
+impl<'a> Debugger<'a> {
+    pub fn new(program: &'a Program) -> Debugger<'a> {
+        Debugger { vm: Vm::new(program) }
+    }
+
+    pub fn vm(&self) -> &Vm<'a> {
+        &self.vm
+    }
+
+    /// Runs one command and returns what to show for it, or `None` to quit.
+    /// Commands can be shortened to their first letter.
+    pub fn command(&mut self, line: &str) -> Option<String> {
+        let words = line.split_whitespace().collect::<Vec<&str>>();
+        let number = |i: usize| words.get(i).map(|word| word.parse::<i64>().map_err(|_| format!("'{}' isn't a number", word)));
+        let output = match words.first().copied().unwrap_or("") {
+            "" => Ok(String::new()),
+            "q" | "quit" => return None,
+            "h" | "help" => Ok(HELP.to_owned()),
+            "s" | "step" => number(1).unwrap_or(Ok(1)).map(|count| {
+                let stop = self.vm.step(count.max(0) as usize);
+                self.status(stop)
+            }),
+            "c" | "continue" => {
+                let stop = self.vm.run();
+                Ok(self.status(stop))
+            },
+            "b" | "break" => match number(1) {
+                Some(Ok(ip)) if ip >= 0 => {
+                    self.vm.set_breakpoint(ip as usize);
+                    let breakpoints = self.vm.breakpoints().map(|ip| ip.to_string()).collect::<Vec<String>>();
+                    Ok(format!("breakpoints at {}", breakpoints.join(", ")))
+                },
+                Some(Err(err)) => Err(err),
+                _ => Err("break needs an instruction".to_owned()),
+            },
+            "d" | "delete" => match number(1) {
+                Some(Ok(ip)) if ip >= 0 && self.vm.clear_breakpoint(ip as usize) => Ok(format!("removed the breakpoint at {}", ip)),
+                Some(Err(err)) => Err(err),
+                _ => Err("there's no breakpoint there".to_owned()),
+            },
+            "w" | "watch" => {
+                self.vm.watch = !self.vm.watch;
+                Ok(format!("{} acc", if self.vm.watch { "watching" } else { "stopped watching" }))
+            },
+            "limit" => match number(1) {
+                Some(Ok(limit)) if limit >= 0 => {
+                    self.vm.set_limit(Some(limit as usize));
+                    Ok(format!("stopping after {} instructions", limit))
+                },
+                None => {
+                    self.vm.set_limit(None);
+                    Ok("no limit".to_owned())
+                },
+                Some(Err(err)) => Err(err),
+                _ => Err("the limit can't be negative".to_owned()),
+            },
+            "r" | "registers" => Ok(self.registers()),
+            "l" | "list" => {
+                let around = number(1).unwrap_or(Ok(self.vm.registers.ip));
+                let count = number(2).unwrap_or(Ok(7));
+                around.and_then(|around| count.map(|count| self.list(around, count.max(1))))
+            },
+            "t" | "trace" => number(1).unwrap_or(Ok(10)).map(|count| self.show_trace(count.max(0) as usize)),
+            "reset" => {
+                self.vm.reset();
+                Ok(self.registers())
+            },
+            command => Err(format!("unknown command '{}', try 'help'", command)),
+        };
+        Some(output.unwrap_or_else(|err| format!("error: {}", err)))
+    }
+
+    fn registers(&self) -> String {
+        let Registers { acc, ip } = self.vm.registers;
+        format!("acc {} ip {} after {} instructions", acc, ip, self.vm.trace.len())
+    }
+
+    fn status(&self, stop: Stop) -> String {
+        let program = self.vm.program;
+        let ip = self.vm.registers.ip;
+        let next = match usize::try_from(ip).ok().and_then(|ip| program.code.get(ip)) {
+            Some(instruction) => format!("\nnext: {}", program.listing_line(ip as usize, instruction)),
+            None => String::new(),
+        };
+        format!("{}: {}{}", stop, self.registers(), next)
+    }
+
+    fn list(&self, around: i64, count: i64) -> String {
+        let program = self.vm.program;
+        let start = (around - count / 2).clamp(0, program.code.len() as i64) as usize;
+        let end = (start + count as usize).min(program.code.len());
+        (start..end).map(|ip| {
+                        let marker = match (ip as i64 == self.vm.registers.ip, self.vm.breakpoints.contains(&ip)) {
+                            (true, true) => "*>",
+                            (true, false) => " >",
+                            (false, true) => "* ",
+                            (false, false) => "  ",
+                        };
+                        format!("{}{}", marker, program.listing_line(ip, &program.code[ip]))
+                    })
+                    .collect::<Vec<String>>()
+                    .join("\n")
+    }
+
+    fn show_trace(&self, count: usize) -> String {
+        let trace = self.vm.trace();
+        trace[trace.len().saturating_sub(count)..].iter()
+                                                  .map(|step| {
+                                                      format!("{:<26} acc {} -> {}",
+                                                              self.vm.program.listing_line(step.ip, &step.instruction),
+                                                              step.before.acc,
+                                                              step.after.acc)
+                                                  })
+                                                  .collect::<Vec<String>>()
+                                                  .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+
+    /// Runs `program` until it either finishes or loops, returning the accumulator
+    /// if it finished and the instructions it ran otherwise.
+    fn run_to_end(program: &Program) -> Result<i64, Vec<usize>> {
+        let mut vm = Vm::new(program);
+        match vm.run() {
+            Stop::Terminated => Ok(vm.registers().acc),
+            _ => Err(vm.trace().iter().map(|step| step.ip).collect()),
+        }
+    }
+
+    fn clone_set_replace(program: &Program, index: usize, op: usize) -> Program {
+        let mut new_program = program.clone();
+        new_program.code[index].op = op;
+        new_program
+    }
+
+    // tries every swap in turn, to check `repairs` against
+    fn part_2_brute(program: &Program) -> Option<i64> {
+        program.code.iter().enumerate().find_map(|(i, instruction)| {
+            let op = swapped(instruction.op)?;
+            run_to_end(&clone_set_replace(program, i, op)).ok()
+        })
+    }
+
+    #[test]
+    fn parse_error_tests() {
+        let err = parse("nop +0\nacc +1\njpm +4").err().unwrap();
+        let operations = vec!["acc".to_owned(), "jmp".to_owned(), "nop".to_owned()];
+        assert_eq!((err.line, err.column, err.expected.clone()), (3, 1, Expected::Operation(operations)));
+        assert_eq!(err.to_string(), "line 3, column 1: expected 'acc', 'jmp' or 'nop', found 'jpm'");
+
+        let err = parse("nop +0\nacc one").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected), (2, 5, Expected::Argument));
+
+        let err = parse("nop +0\nacc").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected, err.found), (2, 4, Expected::Argument, "".to_owned()));
+    }
+
+    #[test]
+    fn vm_tests() {
+        let program = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&program), 5);
+        assert_eq!(part_2_brute(&program), Some(8));
+
+        let mut vm = Vm::new(&program);
+        assert_eq!(vm.run(), Stop::Looped(1));
+        assert_eq!(vm.trace().iter().map(|step| step.ip).collect::<Vec<usize>>(), vec![0, 1, 2, 6, 7, 3, 4]);
+        assert_eq!(vm.trace()[3], Step {
+            ip: 6,
+            instruction: Instruction { op: Opcodes::ACC, arg: 1 },
+            before: Registers { acc: 1, ip: 6 },
+            after: Registers { acc: 2, ip: 7 },
+        });
+
+        vm.reset();
+        vm.set_breakpoint(3);
+        assert_eq!(vm.run(), Stop::Breakpoint(3));
+        assert_eq!(vm.registers(), Registers { acc: 2, ip: 3 });
+        vm.watch_accumulator(true);
+        assert_eq!(vm.run(), Stop::Watch { from: 2, to: 5 });
+        vm.watch_accumulator(false);
+        assert_eq!(vm.step(1), Stop::Stepped);
+        assert_eq!(vm.registers().ip, 1);
+        // the first instruction runs even though it already has
+        assert_eq!(vm.run(), Stop::Looped(2));
+
+        vm.reset();
+        vm.set_limit(Some(4));
+        assert_eq!(vm.step(10), Stop::Limit(4));
+        assert_eq!(vm.trace().len(), 4);
+
+        let program = parse("acc +2\njmp +2\nacc +40\nnop +0").unwrap();
+        let mut vm = Vm::new(&program);
+        assert_eq!(vm.run(), Stop::Terminated);
+        assert_eq!(vm.registers(), Registers { acc: 2, ip: 4 });
+        assert_eq!(vm.step(1), Stop::Terminated);
+        let program = parse("jmp -2").unwrap();
+        assert_eq!(Vm::new(&program).run(), Stop::OutOfBounds(-2));
+
+        let mut opcodes = Opcodes::standard();
+        let mul = opcodes.register("mul", |registers, arg| {
+            registers.acc *= arg;
+            registers.ip += 1;
+        });
+        let program = parse_with("acc +3\nmul -2\nnop +0", opcodes).unwrap();
+        assert_eq!(program.code[1], Instruction { op: mul, arg: -2 });
+        let mut vm = Vm::new(&program);
+        assert_eq!(vm.run(), Stop::Terminated);
+        assert_eq!(vm.registers().acc, -6);
+        let err = parse_with("div +2", program.opcodes.clone()).err().unwrap();
+        assert_eq!(err.to_string(), "line 1, column 1: expected 'acc', 'jmp', 'nop' or 'mul', found 'div'");
+
+        assert_eq!(program.to_string(), "acc +3\nmul -2\nnop +0\n");
+        let program = parse("nop +0\njmp -1").unwrap();
+        assert_eq!(program.disassemble(), "    0  nop +0\n    1  jmp -1       -> 0");
+    }
+
+    #[test]
+    fn debugger_tests() {
+        let program = parse(EXAMPLE).unwrap();
+        let mut debugger = Debugger::new(&program);
+        assert_eq!(debugger.command("break 7").unwrap(), "breakpoints at 7");
+        assert_eq!(debugger.command("c").unwrap(), "breakpoint at 7: acc 2 ip 7 after 4 instructions\nnext:     7  jmp -4       -> 3");
+        assert_eq!(debugger.command("step 2").unwrap(), "stepped: acc 5 ip 4 after 6 instructions\nnext:     4  jmp -3       -> 1");
+        assert_eq!(debugger.command("list 7 3").unwrap(), "      6  acc +1\n*     7  jmp -4       -> 3\n      8  acc +6");
+        assert_eq!(debugger.command("l 4 1").unwrap(), " >    4  jmp -3       -> 1");
+        assert_eq!(debugger.command("trace 2").unwrap(), "    7  jmp -4       -> 3   acc 2 -> 2\n    3  acc +3              acc 2 -> 5");
+        assert_eq!(debugger.command("continue").unwrap(), "about to run 1 a second time: acc 5 ip 1 after 7 instructions\nnext:     1  acc +1");
+        assert_eq!(debugger.command("d 7").unwrap(), "removed the breakpoint at 7");
+        assert_eq!(debugger.command("d 7").unwrap(), "error: there's no breakpoint there");
+        assert_eq!(debugger.command("limit 2").unwrap(), "stopping after 2 instructions");
+        assert_eq!(debugger.command("reset").unwrap(), "acc 0 ip 0 after 0 instructions");
+        assert_eq!(debugger.command("c").unwrap(), "hit the limit of 2 instructions: acc 1 ip 2 after 2 instructions\nnext:     2  jmp +4       -> 6");
+        assert_eq!(debugger.command("step x").unwrap(), "error: 'x' isn't a number");
+        assert_eq!(debugger.command("jump").unwrap(), "error: unknown command 'jump', try 'help'");
+        assert_eq!(debugger.vm().registers(), Registers { acc: 1, ip: 2 });
+        assert_eq!(debugger.command("q"), None);
+    }
+
+    #[test]
+    fn repair_tests() {
+        let program = parse(EXAMPLE).unwrap();
+        let repair = Repair { ip: 7, instruction: Instruction { op: Opcodes::JMP, arg: -4 }, op: Opcodes::NOP, acc: 8 };
+        assert_eq!(repairs(&program), Ok(vec![repair]));
+        assert_eq!(repair.describe(&program), "change 'jmp -4' at 7 to 'nop -4' and it finishes with acc 8");
+        assert_eq!(part_2(&program), Ok(8));
+        assert_eq!(part_2(&program).ok(), part_2_brute(&program));
+
+        let program = parse("nop +3\nacc +1\njmp +0\nacc +5").unwrap();
+        let found = repairs(&program).unwrap();
+        assert_eq!(found.iter().map(|repair| (repair.ip, repair.acc)).collect::<Vec<(usize, i64)>>(), vec![(0, 5), (2, 6)]);
+        assert_eq!(part_2(&program), Ok(5));
+
+        assert_eq!(repairs(&parse("jmp +0\nacc +1\njmp -1").unwrap()), Ok(vec![]));
+        assert_eq!(part_2(&parse("jmp +0\nacc +1\njmp -1").unwrap()), Err("no single change lets the boot code finish".to_owned()));
+        assert_eq!(repairs(&parse("acc +1\nnop +0").unwrap()), Err("the boot code already finishes".to_owned()));
+        assert_eq!(part_2(&parse("acc +1\nnop +0").unwrap()), Err("the boot code already finishes".to_owned()));
+        let mut opcodes = Opcodes::standard();
+        opcodes.register("hlt", |registers, _| registers.ip = i64::MAX);
+        assert_eq!(repairs(&parse_with("nop +0\nhlt +0", opcodes).unwrap()),
+                   Err("can't analyse 'hlt +0' at 1, only acc, jmp and nop".to_owned()));
+
+        // every swap that works by running each swapped program, on programs made up as we go
+        let mut seed: u64 = 8;
+        let mut random = |below: u64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) % below
+        };
+        for _ in 0..200 {
+            let length = 1 + random(30) as usize;
+            let source = (0..length).map(|_| {
+                                        let op = ["acc", "jmp", "nop"][random(3) as usize];
+                                        format!("{} {:+}", op, random(11) as i64 - 5)
+                                    })
+                                    .collect::<Vec<String>>()
+                                    .join("\n");
+            let program = parse(&source).unwrap();
+            if run_to_end(&program).is_ok() {
+                continue;
+            }
+            let expected = program.code.iter()
+                                       .enumerate()
+                                       .filter_map(|(ip, instruction)| {
+                                           let op = swapped(instruction.op)?;
+                                           run_to_end(&clone_set_replace(&program, ip, op)).ok().map(|acc| (ip, acc))
+                                       })
+                                       .collect::<BTreeSet<(usize, i64)>>();
+            let found = repairs(&program).unwrap().iter().map(|repair| (repair.ip, repair.acc)).collect();
+            assert_eq!(expected, found, "{}", source);
+        }
+    }
+}
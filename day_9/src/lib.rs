@@ -0,0 +1,349 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::io::{self, BufRead};
+use std::ops::Range;
+
+use common::Answers;
+
+pub const PREAMBLE: usize = 25;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Number,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Number => write!(f, "a positive whole number"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<Expected>;
+
+pub type Parsed = Vec<u64>;
+
+pub fn parse(input: &str) -> Result<Parsed, ParseError> {
+    input.trim()
+         .split("\n")
+         .map(|number| number.parse::<u64>().map_err(|_| ParseError::at(input, number, Expected::Number)))
+         .collect::<Result<Vec<u64>, ParseError>>()
+}
+
+pub fn solve(input: &str) -> Result<Answers, String> {
+    let numbers = parse(input).map_err(|err| err.to_string())?;
+    Ok(Answers::new(part_1(&numbers)?, part_2(&numbers)?))
+}
+
+pub fn part_1(nums: &[u64]) -> Result<u64, String> {
+    find_outlier(nums, PREAMBLE).ok_or_else(|| "every number is valid".to_owned())
+}
+
+pub fn part_2(nums: &[u64]) -> Result<u64, String> {
+    let outlier = part_1(nums)?;
+    find_weakness(nums, PREAMBLE).ok_or_else(|| format!("no run of numbers adds up to {}", outlier))
+}
+
+/// The first number that isn't the sum of two of the `size` before it.
+pub fn find_outlier(nums: &[u64], size: usize) -> Option<u64> {
+    invalid_numbers(nums.iter().copied(), size).first().map(|invalid| invalid.number)
+}
+
+/// The smallest plus the largest number of the first run of at least two
+/// numbers adding up to the outlier.
+pub fn find_weakness(nums: &[u64], size: usize) -> Option<u64> {
+    let range = ranges_summing_to(nums, find_outlier(nums, size)?).into_iter().next()?;
+    let run = &nums[range];
+    Some(run.iter().min()? + run.iter().max()?)
+}
+
+/// The last `size` numbers, counted by value so checking for a pair that adds
+/// up to a number takes one pass over the window rather than every pair.
+pub struct Window {
+    size: usize,
+    numbers: VecDeque<u64>,
+    counts: HashMap<u64, usize>,
+}
+
+impl Window {
+    pub fn new(size: usize) -> Window {
+        Window { size, numbers: VecDeque::with_capacity(size + 1), counts: HashMap::new() }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.numbers.len() == self.size
+    }
+
+    /// Adds a number, dropping the oldest once the window is full.
+    pub fn push(&mut self, number: u64) {
+        self.numbers.push_back(number);
+        *self.counts.entry(number).or_insert(0) += 1;
+        if self.numbers.len() > self.size {
+            let oldest = self.numbers.pop_front().unwrap();
+            match self.counts.get_mut(&oldest) {
+                Some(count) if *count > 1 => *count -= 1,
+                _ => {
+                    self.counts.remove(&oldest);
+                },
+            }
+        }
+    }
+
+    /// Whether two numbers at different places in the window add up to `sum`.
+    pub fn is_sum(&self, sum: u64) -> bool {
+        self.numbers.iter()
+                    .filter(|&&number| number <= sum)
+                    .any(|&number| {
+                        let other = sum - number;
+                        let needed = if other == number { 2 } else { 1 };
+                        self.counts.get(&other).is_some_and(|&count| count >= needed)
+                    })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Invalid {
+    pub index: usize,
+    pub number: u64,
+}
+
+/// Checks numbers one at a time against the ones just before them.
+pub struct Validator {
+    window: Window,
+    index: usize,
+}
+
+impl Validator {
+    pub fn new(preamble: usize) -> Validator {
+        Validator { window: Window::new(preamble), index: 0 }
+    }
+
+    /// Takes the next number and says whether it's valid. The preamble is
+    /// always valid.
+    pub fn push(&mut self, number: u64) -> Result<(), Invalid> {
+        let valid = !self.window.is_full() || self.window.is_sum(number);
+        let index = self.index;
+        self.window.push(number);
+        self.index += 1;
+        match valid {
+            true => Ok(()),
+            false => Err(Invalid { index, number }),
+        }
+    }
+}
+
+/// Every number that isn't the sum of two of the `preamble` before it.
+pub fn invalid_numbers(numbers: impl IntoIterator<Item = u64>, preamble: usize) -> Vec<Invalid> {
+    let mut validator = Validator::new(preamble);
+    numbers.into_iter()
+           .filter_map(|number| validator.push(number).err())
+           .collect()
+}
+
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::Io(err) => write!(f, "{}", err),
+            ReadError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// A number read from a stream and whether it was valid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Checked {
+    pub index: usize,
+    pub number: u64,
+    pub valid: bool,
+}
+
+/// Numbers read a line at a time and checked as they come in, see `stream`.
+pub struct Stream<R> {
+    lines: io::Lines<R>,
+    line: usize,
+    validator: Validator,
+}
+
+/// Reads and checks one number per line of `reader` without holding on to
+/// more than the window. Blank lines are skipped.
+pub fn stream<R: BufRead>(reader: R, preamble: usize) -> Stream<R> {
+    Stream { lines: reader.lines(), line: 0, validator: Validator::new(preamble) }
+}
+
+impl<R: BufRead> Iterator for Stream<R> {
+    type Item = Result<Checked, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line += 1;
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(err) => return Some(Err(ReadError::Io(err))),
+            };
+            let number = line.trim();
+            if number.is_empty() {
+                continue;
+            }
+            let number = match number.parse::<u64>() {
+                Ok(number) => number,
+                Err(_) => {
+                    let column = line.find(number).unwrap_or(0) + 1;
+                    let err = ParseError { line: self.line, column, expected: Expected::Number, found: number.to_owned() };
+                    return Some(Err(ReadError::Parse(err)));
+                },
+            };
+            let index = self.validator.index;
+            let valid = self.validator.push(number).is_ok();
+            return Some(Ok(Checked { index, number, valid }));
+        }
+    }
+}
+
+/// Every run of two or more numbers in a row adding up to `target`, by where
+/// they start and then where they end.
+///
+/// Keeps the running total up to each number; a run ending at `j` adds up to
+/// `target` when the total before it started was the total so far less
+/// `target`.
+pub fn ranges_summing_to(nums: &[u64], target: u64) -> Vec<Range<usize>> {
+    let target = target as u128;
+    // the numbers are never negative so the same total only turns up across zeros
+    let mut starts: HashMap<u128, Vec<usize>> = HashMap::new();
+    let mut total: u128 = 0;
+    let mut ranges = Vec::new();
+    for (end, &number) in nums.iter().enumerate() {
+        starts.entry(total).or_default().push(end);
+        total += number as u128;
+        if total >= target {
+            if let Some(found) = starts.get(&(total - target)) {
+                ranges.extend(found.iter().filter(|&&start| end > start).map(|&start| start..end + 1));
+            }
+        }
+    }
+    ranges.sort_by_key(|range| (range.start, range.end));
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_test() {
+        let nums: Vec<u64> = vec![
+            1,
+            2,
+            3,
+            4,
+            5,
+            6,
+            7,
+            8,
+            9,
+            10,
+            11,
+            12,
+            13,
+            14,
+            15,
+            16,
+            17,
+            18,
+            19,
+            20,
+            21,
+            22,
+            23,
+            24,
+            25,
+            26,
+            49,
+            100
+        ];
+        assert_eq!(find_outlier(&nums, 25_usize), Some(100));
+    }
+
+    #[test]
+    fn part_2_test() {
+        let nums: Vec<u64> = vec![
+            35,
+            20,
+            15,
+            25,
+            47,
+            40,
+            62,
+            55,
+            65,
+            95,
+            102,
+            117,
+            150,
+            182,
+            127,
+            219,
+            299,
+            277,
+            309,
+            576,
+        ];
+        assert_eq!(find_weakness(&nums, 5), Some(62));
+    }
+
+    #[test]
+    fn stream_tests() {
+        let input = "35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127\n219\n299\n277\n309\n576\n";
+        let checked = stream(input.as_bytes(), 5).collect::<Result<Vec<Checked>, ReadError>>().unwrap();
+        assert_eq!(checked.len(), 20);
+        assert_eq!(checked.iter().filter(|checked| !checked.valid).map(|checked| checked.index).collect::<Vec<usize>>(), vec![14]);
+        assert_eq!(checked[14], Checked { index: 14, number: 127, valid: false });
+
+        let nums = parse(input).unwrap();
+        assert_eq!(invalid_numbers(nums.iter().copied(), 5), vec![Invalid { index: 14, number: 127 }]);
+        for preamble in 1..8 {
+            let brute = (preamble..nums.len()).filter(|&i| {
+                                                  let window = &nums[i - preamble..i];
+                                                  !(0..preamble).any(|a| (a + 1..preamble).any(|b| window[a] + window[b] == nums[i]))
+                                              })
+                                              .collect::<Vec<usize>>();
+            let found = invalid_numbers(nums.iter().copied(), preamble).iter().map(|invalid| invalid.index).collect::<Vec<usize>>();
+            assert_eq!(found, brute);
+        }
+
+        // a number twice in the window can add up with itself, a single one can't
+        let mut window = Window::new(3);
+        for number in [4, 1, 4] {
+            window.push(number);
+        }
+        assert!(window.is_sum(8) && window.is_sum(5) && !window.is_sum(2));
+        window.push(9);
+        assert!(!window.is_sum(8) && window.is_sum(13) && window.is_sum(5));
+        assert_eq!(invalid_numbers(vec![3, 3, 6, 9, 15, 4, 19, 1], 2), vec![Invalid { index: 5, number: 4 }, Invalid { index: 7, number: 1 }]);
+
+        let err = stream("1\n2\n\n 3x\n".as_bytes(), 2).nth(2).unwrap().err().unwrap();
+        assert_eq!(err.to_string(), "line 4, column 2: expected a positive whole number, found '3x'");
+        assert_eq!(find_outlier(&[1, 2, 3], 2), None);
+        assert_eq!(find_weakness(&[1, 2, 5], 2), None);
+        assert_eq!(part_1(&[1, 2, 5]), Err("every number is valid".to_owned()));
+        assert_eq!(part_2(&[1, 2, 5]), Err("every number is valid".to_owned()));
+        let nums = (1..=25).chain([1000]).collect::<Vec<u64>>();
+        assert_eq!(part_1(&nums), Ok(1000));
+        assert_eq!(part_2(&nums), Err("no run of numbers adds up to 1000".to_owned()));
+    }
+
+    #[test]
+    fn range_tests() {
+        let nums = [35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127];
+        assert_eq!(ranges_summing_to(&nums, 127), vec![2..6]);
+        assert_eq!(ranges_summing_to(&[1, 2, 3, 3, 0, 3], 6), vec![0..3, 2..4, 2..5, 3..6]);
+        assert_eq!(ranges_summing_to(&[6, 6], 6), Vec::<Range<usize>>::new());
+        assert_eq!(ranges_summing_to(&[], 6), Vec::<Range<usize>>::new());
+    }
+}